@@ -0,0 +1,49 @@
+/* Copyright (C) 2022  Fabian Moos
+ * This file is part of encodex.
+ *
+ * encodex is free software: you can redistribute it and/or modify it under the terms of the GNU
+ * General Public License as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * encodex is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without
+ * even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+ * General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along with encodex. If not,
+ * see <https://www.gnu.org/licenses/>.
+ */
+
+
+//! Benchmarks the encoding of large buffers.
+//!
+//! Compare the scalar and the `simd` encoder by saving a baseline without the feature and
+//! measuring against it with the feature enabled:
+//!
+//! ```text
+//! cargo bench --bench encode -- --save-baseline scalar
+//! cargo bench --bench encode --features simd -- --baseline scalar
+//! ```
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use encodex::{Base, Settings, TranslationUnit};
+
+const BUFFER_SIZE: usize = 10 * 1024 * 1024;
+
+fn bench_encode_base64(c: &mut Criterion) {
+    let data: Vec<u8> = (0..BUFFER_SIZE).map(|index| (index * 31 % 251) as u8).collect();
+    let mut config = Settings::new();
+    config.set_base(Base::Base64);
+
+    let mut group = c.benchmark_group("encode");
+    group.throughput(Throughput::Bytes(BUFFER_SIZE as u64));
+    group.sample_size(10);
+    group.bench_function("base64_10mb", |b| {
+        b.iter_batched(|| TranslationUnit::new(data.clone(), config.clone()),
+                       |mut unit| unit.translate().unwrap(),
+                       BatchSize::LargeInput);
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_encode_base64);
+criterion_main!(benches);
@@ -13,26 +13,67 @@
  * see <https://www.gnu.org/licenses/>.
  */
 
-use std::{env, path, process};
+use std::{env, io, path, process};
 
 use crate::input::{Input, ReadMode};
+use crate::output::{OutputFormat, HEXDUMP_LINE_LEN};
 use encodex::{Base, EncodeMode, Settings};
 
+const OP_ALPHABET_LONG: &str = "alphabet";
 const OP_BASE: &str = "b";
 const OP_BASE_LONG: &str = "base";
+const OP_CHECK_LONG: &str = "check";
+const OP_CONCAT_LONG: &str = "concat";
 const OP_DECODE: &str = "d";
 const OP_DECODE_LONG: &str = "decode";
 const OP_HELP_LONG: &str = "help";
+const OP_HEXDUMP_LONG: &str = "hexdump";
+const OP_KEEP_GOING_LONG: &str = "keep-going";
+const OP_LABELED_LONG: &str = "labeled";
+const OP_SPLIT_OUTPUT_LONG: &str = "split-output";
+const OP_STRICT_ARGS_LONG: &str = "strict-args";
+const OP_VERIFY_DIGEST_LONG: &str = "verify-digest";
 const OP_VERSION_LONG: &str = "version";
+const OP_WITH_DIGEST_LONG: &str = "with-digest";
 
-pub fn parse_terminal_args() -> Result<(Input, Settings), String> {
+/// The approximate number of bytes that are read from stdin at once.
+const STDIN_BLOCK_LEN: usize = 64 * 1024;
+
+/// Describes what happens when an input can't be translated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorPolicy {
+    /// Exit with 1 at the first error.
+    FailFast,
+    /// Print the error, continue with the next input and exit with 1 after the last one.
+    KeepGoing,
+}
+
+/// The inputs, the configuration, the output format and the error policy given on the command
+/// line.
+pub type ParsedArgs = (Input, Settings, OutputFormat, ErrorPolicy);
+
+pub fn parse_terminal_args() -> Result<ParsedArgs, String> {
+    parse_args(env::args().skip(1))
+}
+
+#[allow(clippy::partialeq_to_none, clippy::len_zero, clippy::single_char_add_str)]
+fn parse_args<I: Iterator<Item = String>>(mut arg_it: I) -> Result<ParsedArgs, String> {
     let working_dir = match env::current_dir() {
         Ok(path) => { path }
         Err(error) => { panic!("{}", error); }
     };
     let mut settings = Settings::new();
     let mut input = Input::new();
-    let mut arg_it = env::args().skip(1);
+    let mut parsed_bases: Vec<Base> = Vec::new();
+    let mut strict_args = false;
+    let mut concat = false;
+    let mut labeled = false;
+    let mut print_alphabet = false;
+    let mut with_digest = false;
+    let mut verify_digest = false;
+    let mut output_format = OutputFormat::Raw;
+    let mut error_policy = ErrorPolicy::FailFast;
+    let mut split_output: Option<usize> = None;
     let mut arg_opt = arg_it.next();
 
     while arg_opt != None {
@@ -55,20 +96,40 @@ pub fn parse_terminal_args() -> Result<(Input, Settings), String> {
         }
 
         match current_value {
+            OP_ALPHABET_LONG if long_cmd_line_op => { print_alphabet = true; }
             OP_BASE_LONG if long_cmd_line_op => {
                 if let Err(error_message) = handle_base_type(&mut settings, arg_it.next()) {
                     return Err(String::from(error_message));
                 }
+                parsed_bases.push(settings.base());
             }
             OP_BASE if short_cmd_line_op => {
                 if let Err(error_message) = handle_base_type(&mut settings, arg_it.next()) {
                     return Err(String::from(error_message));
                 }
+                parsed_bases.push(settings.base());
             }
+            OP_CHECK_LONG if long_cmd_line_op => { output_format = OutputFormat::Check; }
+            OP_CONCAT_LONG if long_cmd_line_op => { concat = true; }
             OP_DECODE_LONG if long_cmd_line_op => { switch_encode_mode(&mut settings); }
             OP_DECODE if short_cmd_line_op => { switch_encode_mode(&mut settings); }
             OP_HELP_LONG if long_cmd_line_op => { print_help(); process::exit(0); }
+            OP_HEXDUMP_LONG if long_cmd_line_op => { output_format = OutputFormat::Hexdump; }
+            OP_SPLIT_OUTPUT_LONG if long_cmd_line_op => {
+                match arg_it.next().and_then(|value| value.parse::<usize>().ok()) {
+                    Some(max_len) => { split_output = Some(max_len); }
+                    None => {
+                        return Err(String::from(
+                            ">>> Error: '--split-output' needs a number of bytes!"));
+                    }
+                }
+            }
+            OP_KEEP_GOING_LONG if long_cmd_line_op => { error_policy = ErrorPolicy::KeepGoing; }
+            OP_LABELED_LONG if long_cmd_line_op => { labeled = true; }
+            OP_STRICT_ARGS_LONG if long_cmd_line_op => { strict_args = true; }
+            OP_VERIFY_DIGEST_LONG if long_cmd_line_op => { verify_digest = true; }
             OP_VERSION_LONG if long_cmd_line_op => { print_version(); process::exit(0); }
+            OP_WITH_DIGEST_LONG if long_cmd_line_op => { with_digest = true; }
             "" => { input.switch_read_mode(); }
             &_ if !long_cmd_line_op && !short_cmd_line_op => {
                 handle_input(&mut input, current_value, &working_dir);
@@ -83,7 +144,103 @@ pub fn parse_terminal_args() -> Result<(Input, Settings), String> {
         }
         arg_opt = arg_it.next();
     }
-    Ok((input, settings))
+    if strict_args && parsed_bases.windows(2).any(|bases| bases[0] != bases[1]) {
+        return Err(String::from(">>> Error: Conflicting values for '--base' option!"));
+    }
+    if print_alphabet {
+        match alphabet_table(settings.base()) {
+            Some(table) => { print!("{}", table); process::exit(0); }
+            None => {
+                return Err(String::from(">>> Error: '--alphabet' requires a '--base' option!"));
+            }
+        }
+    }
+    if with_digest && settings.encode_mode() == EncodeMode::Decode {
+        return Err(String::from(">>> Error: '--with-digest' can only be used for encoding!"));
+    }
+    if verify_digest && settings.encode_mode() == EncodeMode::Encode {
+        return Err(String::from(">>> Error: '--verify-digest' can only be used for decoding!"));
+    }
+    if output_format == OutputFormat::Hexdump && settings.encode_mode() == EncodeMode::Encode {
+        return Err(String::from(">>> Error: '--hexdump' can only be used for decoding!"));
+    }
+    if output_format == OutputFormat::Check && settings.encode_mode() == EncodeMode::Encode {
+        return Err(String::from(">>> Error: '--check' can only be used for decoding!"));
+    }
+    if let Some(max_len) = split_output {
+        output_format = OutputFormat::Split(split_output_len(&settings, max_len, with_digest)?);
+    }
+    settings.set_digest_line(with_digest || verify_digest);
+    if settings.encode_mode() == EncodeMode::Decode { input.strip_trailing_newlines(); }
+    if concat { input.concatenate_byte_streams(); }
+    if !labeled { input.clear_labels(); }
+    if input.is_empty() { input.add_stdin(Box::new(io::stdin()), stdin_block_len(&settings)); }
+    Ok((input, settings, output_format, error_policy))
+}
+
+/// Returns the length of the blocks that stdin is read in. The blocks are aligned to the groups
+/// of the base, so no group spans two blocks and only the last block is padded. Decoded blocks
+/// also hold whole lines of a hex dump. Digest lines, guessing the base and bases without fixed
+/// groups need all of stdin at once.
+fn stdin_block_len(settings: &Settings) -> usize {
+    let chunk_size = settings.base().chunk_size();
+    if settings.digest_line() || chunk_size == usize::MAX {
+        return usize::MAX;
+    }
+    match settings.encode_mode() {
+        EncodeMode::Encode => { std::cmp::max(STDIN_BLOCK_LEN / chunk_size, 1) * chunk_size }
+        EncodeMode::Decode if settings.base() == Base::Guess => { usize::MAX }
+        EncodeMode::Decode => {
+            let encoded_chunk_size = settings.base().encoded_len(chunk_size).unwrap();
+            let groups = STDIN_BLOCK_LEN / encoded_chunk_size / HEXDUMP_LINE_LEN;
+            std::cmp::max(groups, 1) * HEXDUMP_LINE_LEN * encoded_chunk_size
+        }
+    }
+}
+
+/// Returns the largest number of bytes up to `max_len` that is a whole number of encoded groups,
+/// so that every file of the split output decodes on its own.
+fn split_output_len(settings: &Settings, max_len: usize, with_digest: bool)
+                    -> Result<usize, String> {
+    if settings.encode_mode() == EncodeMode::Decode {
+        return Err(String::from(">>> Error: '--split-output' can only be used for encoding!"));
+    }
+    if with_digest {
+        return Err(String::from(
+            ">>> Error: '--split-output' can't be combined with '--with-digest'!"));
+    }
+    let base = settings.base();
+    let group_len = match base.encoded_len(base.chunk_size()) {
+        Ok(group_len) if base.chunk_size() != usize::MAX => { group_len }
+        _ => {
+            return Err(format!(">>> Error: '--split-output' needs a base with fixed groups, \
+                                {} has none!", base));
+        }
+    };
+    if max_len < group_len {
+        return Err(format!(">>> Error: '--split-output' needs at least {} bytes for {}!",
+                           group_len, base));
+    }
+    Ok(max_len / group_len * group_len)
+}
+
+/// Formats the alphabet and the padding character of `base` as a table with four columns.
+fn alphabet_table(base: Base) -> Option<String> {
+    let alphabet = base.alphabet()?;
+    let rows = alphabet.len().div_ceil(4);
+    let mut table = format!("{} alphabet:\n", base);
+    for row in 0..rows {
+        let mut line = String::new();
+        for value in (row..alphabet.len()).step_by(rows) {
+            line.push_str(&format!("{:>5}  {}    ", value, &alphabet[value..value + 1]));
+        }
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    if let Some(padding) = base.padding() {
+        table.push_str(&format!("(pad)  {}\n", padding));
+    }
+    Some(table)
 }
 
 fn handle_base_type(settings: &mut Settings, base_type: Option<String>)
@@ -96,6 +253,12 @@ fn handle_base_type(settings: &mut Settings, base_type: Option<String>)
                 "Base32" => { settings.set_base(Base::Base32); Ok(()) }
                 "Base32hex" => { settings.set_base(Base::Base32hex); Ok(()) }
                 "Base16" => { settings.set_base(Base::Base16); Ok(()) }
+                "ImapUtf7" => { settings.set_base(Base::ImapUtf7); Ok(()) }
+                "Crockford32" => { settings.set_base(Base::Crockford32); Ok(()) }
+                "Base45" => { settings.set_base(Base::Base45); Ok(()) }
+                "FilenameSafe" => { settings.set_base(Base::FilenameSafe); Ok(()) }
+                "Base32Geohash" => { settings.set_base(Base::Base32Geohash); Ok(()) }
+                "WebToken" => { settings.set_base(Base::WebToken); Ok(()) }
                 &_ => { Err(">>> Error: Unrecognized base type!") }
             }
         }
@@ -103,12 +266,13 @@ fn handle_base_type(settings: &mut Settings, base_type: Option<String>)
     }
 }
 
+#[allow(clippy::ptr_arg)]
 fn handle_input(input: &mut Input, value: &str, working_dir: &path::PathBuf) {
     match input.read_mode() {
         ReadMode::FileName => {
             let mut file_path = working_dir.clone();
             file_path.push(value);
-            input.add_file(file_path);
+            input.add_named_file(String::from(value), file_path);
         }
         ReadMode::StdIn => {
             input.add_string_as_byte_stream(String::from(value));
@@ -122,21 +286,58 @@ fn print_help() {
     println!("  The default of the program is encoding input and printing it to stdout.");
     println!("  Every command line argument that is not prefixed with '-' or '--' and is not");
     println!("  empty will be interpreted as a file name to be encoded/decoded. '--' without any");
-    println!("  suffix switches between file input and stdin.\n");
+    println!("  suffix switches between file input and stdin. Without any input, the input is");
+    println!("  read from stdin.\n");
     println!("Options:");
-    println!("  -{}, --{} <base>      Set encoding to: Base64, Base64url, Base32(todo),",
+    println!("      --{}         Print the alphabet of the base given with --{} and exit",
+             OP_ALPHABET_LONG, OP_BASE_LONG);
+    println!("  -{}, --{} <base>      Set encoding to: Base64, Base64url, Base32, Base32hex,",
              OP_BASE, OP_BASE_LONG);
-    println!("                         Base32hex(todo), Base16(todo). Default is 'Guess Base' (todo).");
+    println!("                         Base16, Base45, ImapUtf7, Crockford32, FilenameSafe,");
+    println!("                         Base32Geohash, WebToken.");
+    println!("                         Default is 'Guess Base' (todo).");
+    println!("      --{}            Only check that the input can be decoded, exit with 1 and",
+             OP_CHECK_LONG);
+    println!("                         an error message if not");
+    println!("      --{}           Treat all inputs as one continuous input", OP_CONCAT_LONG);
     println!("  -{}, --{}           Decode input",
              OP_DECODE, OP_DECODE_LONG);
     println!("      --{}             Print this help and exit", OP_HELP_LONG);
-    println!("      --{}          Print version and license information and exit\n",
+    println!("      --{}          Print the decoded output as an 'xxd' style hex dump",
+             OP_HEXDUMP_LONG);
+    println!("      --{}       Continue with the next input after an error and exit with 1",
+             OP_KEEP_GOING_LONG);
+    println!("                         after the last one");
+    println!("      --{}          Print a line '# <file>' before the output of every input",
+             OP_LABELED_LONG);
+    println!("                         file");
+    println!("      --{} <bytes>", OP_SPLIT_OUTPUT_LONG);
+    println!("                         Write the encoded output to the files 'encodex-split.000',");
+    println!("                         'encodex-split.001', ... of at most <bytes> bytes each");
+    println!("      --{}      Fail if --{} is given more than once with different values",
+             OP_STRICT_ARGS_LONG, OP_BASE_LONG);
+    println!("      --{}    Check the digest line of the decoded input",
+             OP_VERIFY_DIGEST_LONG);
+    println!("      --{}          Print version and license information and exit",
              OP_VERSION_LONG);
+    println!("      --{}      Append a digest line to the encoded output\n",
+             OP_WITH_DIGEST_LONG);
     println!("The last parsed value for the -{} option determines the used base for encoding and",
              OP_BASE);
-    println!(" decoding.");
+    println!(" decoding, unless --{} is given.", OP_STRICT_ARGS_LONG);
+    println!("The split output files are created in the working directory. Their size is rounded");
+    println!(" down to whole encoded groups, so every file decodes on its own and the files");
+    println!(" decode in numbered order when concatenated.");
+    println!("Stdin is read and translated in blocks, so encodex works as a filter in a pipe with");
+    println!(" constant memory. A single trailing line break is removed from decoded input.");
+    println!(" The output of stdin is continuous, so --{} stops at the first block of stdin",
+             OP_KEEP_GOING_LONG);
+    println!(" that fails.");
+    println!("The digest line has the format '# sha256:<hex>' if encodex is built with the 'sha2'");
+    println!(" feature and '# crc32:<hex>' otherwise.");
 }
 
+#[allow(clippy::single_char_add_str)]
 fn print_version() {
     let program_name = String::from(env!("CARGO_PKG_NAME"));
     let mut version = String::from(env!("CARGO_PKG_VERSION_MAJOR"));
@@ -168,3 +369,217 @@ fn switch_encode_mode(settings: &mut Settings) {
     }
 }
 
+/// Tests for parsing command line arguments.
+#[cfg(any(test, feature = "doc_tests"))]
+mod tests {
+    use super::*;
+
+    fn to_args(args: &[&str]) -> std::vec::IntoIter<String> {
+        args.iter().map(|arg| String::from(*arg)).collect::<Vec<String>>().into_iter()
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_parse_args_base_last_value_wins() {
+        let result = parse_args(to_args(&["-b", "Base64", "--base", "Base64url"]));
+        let (_, settings, _, _) = result.ok().unwrap();
+        assert_eq!(settings.base(), Base::Base64url);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_parse_args_strict_args_conflicting_base() {
+        let result = parse_args(to_args(&["--strict-args", "-b", "Base64", "-b", "Base32"]));
+        assert_eq!(result.err(),
+                   Some(String::from(">>> Error: Conflicting values for '--base' option!")));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_parse_args_strict_args_after_conflicting_base() {
+        let result = parse_args(to_args(&["-b", "Base64", "-b", "Base32", "--strict-args"]));
+        assert_eq!(result.err(),
+                   Some(String::from(">>> Error: Conflicting values for '--base' option!")));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_parse_args_strict_args_repeated_base() {
+        let result = parse_args(to_args(&["--strict-args", "-b", "Base64", "--base", "Base64"]));
+        let (_, settings, _, _) = result.ok().unwrap();
+        assert_eq!(settings.base(), Base::Base64);
+    }
+
+    #[cfg(feature = "base64")]
+    fn encode_next_byte_stream(input: &mut Input, settings: &Settings) -> Option<String> {
+        let bytes = input.get_next_byte_stream()?;
+        let mut unit = encodex::TranslationUnit::new(bytes, settings.clone());
+        unit.translate().ok()?;
+        Some(String::from_utf8(unit.get_encoded_data().clone().unwrap()).unwrap())
+    }
+
+    #[cfg(feature = "base64")]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_parse_args_concat() {
+        let result = parse_args(to_args(&["--concat", "-b", "Base64", "--", "f", "oo", "bar"]));
+        let (mut input, settings, _, _) = result.ok().unwrap();
+        assert_eq!(encode_next_byte_stream(&mut input, &settings), Some(String::from("Zm9vYmFy")));
+        assert_eq!(encode_next_byte_stream(&mut input, &settings), None);
+    }
+
+    #[cfg(feature = "base64")]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_parse_args_without_concat() {
+        let result = parse_args(to_args(&["-b", "Base64", "--", "f", "oo"]));
+        let (mut input, settings, _, _) = result.ok().unwrap();
+        assert_eq!(encode_next_byte_stream(&mut input, &settings), Some(String::from("b28=")));
+        assert_eq!(encode_next_byte_stream(&mut input, &settings), Some(String::from("Zg==")));
+        assert_eq!(encode_next_byte_stream(&mut input, &settings), None);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_parse_args_labeled() {
+        let args = ["--labeled", "-b", "Base64", "tests/fixtures/foobar.txt", "--", "foo"];
+        let (mut input, _, _, _) = parse_args(to_args(&args)).ok().unwrap();
+        assert!(input.get_next_byte_stream().is_some());
+        assert_eq!(input.current_label(), None);
+        assert!(input.get_next_byte_stream().is_some());
+        assert_eq!(input.current_label(), Some("tests/fixtures/foobar.txt"));
+
+        let (mut input, _, _, _) = parse_args(to_args(&args[1..])).ok().unwrap();
+        input.get_next_byte_stream();
+        assert!(input.get_next_byte_stream().is_some());
+        assert_eq!(input.current_label(), None);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_parse_args_with_digest() {
+        let result = parse_args(to_args(&["--with-digest", "-b", "Base64"]));
+        let (_, settings, _, _) = result.ok().unwrap();
+        assert!(settings.digest_line());
+
+        let result = parse_args(to_args(&["-d", "--with-digest", "-b", "Base64"]));
+        assert_eq!(result.err(), Some(String::from(
+            ">>> Error: '--with-digest' can only be used for encoding!")));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_parse_args_verify_digest() {
+        let result = parse_args(to_args(&["-d", "--verify-digest", "-b", "Base64"]));
+        let (_, settings, _, _) = result.ok().unwrap();
+        assert!(settings.digest_line());
+
+        let result = parse_args(to_args(&["--verify-digest", "-b", "Base64"]));
+        assert_eq!(result.err(), Some(String::from(
+            ">>> Error: '--verify-digest' can only be used for decoding!")));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_parse_args_hexdump() {
+        let result = parse_args(to_args(&["-d", "--hexdump", "-b", "Base64"]));
+        let (_, _, output_format, _) = result.ok().unwrap();
+        assert_eq!(output_format, OutputFormat::Hexdump);
+
+        let result = parse_args(to_args(&["-d", "-b", "Base64"]));
+        let (_, _, output_format, _) = result.ok().unwrap();
+        assert_eq!(output_format, OutputFormat::Raw);
+
+        let result = parse_args(to_args(&["--hexdump", "-b", "Base64"]));
+        assert_eq!(result.err(), Some(String::from(
+            ">>> Error: '--hexdump' can only be used for decoding!")));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_parse_args_keep_going() {
+        let result = parse_args(to_args(&["--keep-going", "-b", "Base64"]));
+        let (_, _, _, error_policy) = result.ok().unwrap();
+        assert_eq!(error_policy, ErrorPolicy::KeepGoing);
+
+        let result = parse_args(to_args(&["-b", "Base64"]));
+        let (_, _, _, error_policy) = result.ok().unwrap();
+        assert_eq!(error_policy, ErrorPolicy::FailFast);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_parse_args_check() {
+        let result = parse_args(to_args(&["-d", "--check", "-b", "Base64"]));
+        let (_, _, output_format, _) = result.ok().unwrap();
+        assert_eq!(output_format, OutputFormat::Check);
+
+        let result = parse_args(to_args(&["--check", "-b", "Base64"]));
+        assert_eq!(result.err(), Some(String::from(
+            ">>> Error: '--check' can only be used for decoding!")));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_parse_args_split_output() {
+        let result = parse_args(to_args(&["--split-output", "10", "-b", "Base64"]));
+        let (_, _, output_format, _) = result.ok().unwrap();
+        assert_eq!(output_format, OutputFormat::Split(8));
+
+        let result = parse_args(to_args(&["--split-output", "17", "-b", "Base32"]));
+        let (_, _, output_format, _) = result.ok().unwrap();
+        assert_eq!(output_format, OutputFormat::Split(16));
+
+        let result = parse_args(to_args(&["--split-output", "3", "-b", "Base64"]));
+        assert_eq!(result.err(), Some(String::from(
+            ">>> Error: '--split-output' needs at least 4 bytes for Base64!")));
+
+        let result = parse_args(to_args(&["--split-output", "ten", "-b", "Base64"]));
+        assert_eq!(result.err(), Some(String::from(
+            ">>> Error: '--split-output' needs a number of bytes!")));
+
+        let result = parse_args(to_args(&["-d", "--split-output", "10", "-b", "Base64"]));
+        assert_eq!(result.err(), Some(String::from(
+            ">>> Error: '--split-output' can only be used for encoding!")));
+
+        let result = parse_args(to_args(&["--split-output", "10", "-b", "Crockford32"]));
+        assert_eq!(result.err(), Some(String::from(
+            ">>> Error: '--split-output' needs a base with fixed groups, Crockford32 has none!")));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_stdin_block_len() {
+        let mut settings = Settings::new();
+        settings.set_base(Base::Base64);
+        assert_eq!(stdin_block_len(&settings), 65535);
+        settings.set_base(Base::Base32);
+        assert_eq!(stdin_block_len(&settings), 65535);
+        settings.set_base(Base::Base16);
+        assert_eq!(stdin_block_len(&settings), STDIN_BLOCK_LEN);
+        settings.set_base(Base::Crockford32);
+        assert_eq!(stdin_block_len(&settings), usize::MAX);
+        settings.set_base(Base::Base64);
+        settings.set_digest_line(true);
+        assert_eq!(stdin_block_len(&settings), usize::MAX);
+        settings.set_digest_line(false);
+        settings.set_encode_mode(EncodeMode::Decode);
+        assert_eq!(stdin_block_len(&settings), STDIN_BLOCK_LEN);
+        settings.set_base(Base::Base45);
+        assert_eq!(stdin_block_len(&settings), 65520);
+        settings.set_base(Base::Crockford32);
+        assert_eq!(stdin_block_len(&settings), usize::MAX);
+        settings.set_base(Base::Guess);
+        assert_eq!(stdin_block_len(&settings), usize::MAX);
+        settings.set_base(Base::Base32);
+        settings.set_digest_line(true);
+        assert_eq!(stdin_block_len(&settings), usize::MAX);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_alphabet_table_base64() {
+        let table = alphabet_table(Base::Base64).unwrap();
+        let mut lines = table.lines();
+        assert_eq!(lines.next(), Some("Base64 alphabet:"));
+        let entries: Vec<&str> = lines.flat_map(|line| line.split_whitespace()).collect();
+        let mut pairs: Vec<(&str, &str)> = entries.chunks(2)
+                                                  .map(|entry| (entry[0], entry[1]))
+                                                  .collect();
+        assert_eq!(pairs.len(), 64 + 1);
+        assert_eq!(pairs.pop(), Some(("(pad)", "=")));
+        pairs.sort_by_key(|(value, _)| value.parse::<usize>().unwrap());
+        let characters: String = pairs.iter().map(|(_, character)| *character).collect();
+        assert_eq!(characters, Base::Base64.alphabet().unwrap());
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_alphabet_table_guess() {
+        assert_eq!(alphabet_table(Base::Guess), None);
+    }
+}
@@ -0,0 +1,188 @@
+/* Copyright (C) 2022  Fabian Moos
+ * This file is part of encodex.
+ *
+ * encodex is free software: you can redistribute it and/or modify it under the terms of the GNU
+ * General Public License as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * encodex is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without
+ * even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+ * General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along with encodex. If not,
+ * see <https://www.gnu.org/licenses/>.
+ */
+
+
+//! Checksums that can be computed while translating data.
+
+/// Describes the available checksum algorithms.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChecksumKind {
+    /// The CRC-32 checksum as used by zlib, gzip and PNG (polynomial `0x04C11DB7`).
+    Crc32,
+    /// The Adler-32 checksum as defined by **RFC 1950**.
+    Adler32,
+}
+
+/// Lookup table for the reflected CRC-32 polynomial.
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut value = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            value = if value & 1 == 1 { (value >> 1) ^ 0xEDB88320 } else { value >> 1 };
+            bit += 1;
+        }
+        table[index] = value;
+        index += 1;
+    }
+    table
+}
+
+/// Modulus of the Adler-32 checksum, the largest prime below 2^16.
+const ADLER32_MODULUS: u32 = 65521;
+
+/// The state of a checksum that is computed incrementally.
+pub(crate) struct Checksum {
+    kind: ChecksumKind,
+    state: u32,
+    /// Whether the bits of every byte are reversed before it's added.
+    reverse_bits: bool,
+}
+
+impl Checksum {
+    /// Creates the initial state for a checksum of the given kind.
+    pub(crate) fn new(kind: ChecksumKind) -> Checksum {
+        let state = match kind {
+            ChecksumKind::Crc32 => { 0xFFFFFFFF }
+            ChecksumKind::Adler32 => { 1 }
+        };
+        Checksum { kind, state, reverse_bits: false }
+    }
+
+    /// Reverses the bits of every byte before it's added, so the checksum of data whose bits
+    /// have been reversed for the [`LsbFirst`](crate::BitOrder::LsbFirst) bit order is the one of
+    /// the original data.
+    pub(crate) fn set_reverse_bits(&mut self, reverse_bits: bool) {
+        self.reverse_bits = reverse_bits;
+    }
+
+    /// Discards all bytes that have been added.
+    pub(crate) fn reset(&mut self) {
+        let reverse_bits = self.reverse_bits;
+        *self = Checksum::new(self.kind);
+        self.reverse_bits = reverse_bits;
+    }
+
+    /// Adds `bytes` to the checksum.
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        match self.kind {
+            ChecksumKind::Crc32 => {
+                for byte in bytes {
+                    let byte = if self.reverse_bits { byte.reverse_bits() } else { *byte };
+                    let index = ((self.state ^ byte as u32) & 0xFF) as usize;
+                    self.state = (self.state >> 8) ^ CRC32_TABLE[index];
+                }
+            }
+            ChecksumKind::Adler32 => {
+                let mut a = self.state & 0xFFFF;
+                let mut b = self.state >> 16;
+                for byte in bytes {
+                    let byte = if self.reverse_bits { byte.reverse_bits() } else { *byte };
+                    a = (a + byte as u32) % ADLER32_MODULUS;
+                    b = (b + a) % ADLER32_MODULUS;
+                }
+                self.state = (b << 16) | a;
+            }
+        }
+    }
+
+    /// Returns the checksum of all bytes that have been added.
+    pub(crate) fn finish(&self) -> u32 {
+        match self.kind {
+            ChecksumKind::Crc32 => { !self.state }
+            ChecksumKind::Adler32 => { self.state }
+        }
+    }
+}
+
+/// The name of the algorithm that is used for [digest lines](crate::Settings::set_digest_line).
+#[cfg(feature = "sha2")]
+pub(crate) const DIGEST_ALGORITHM: &str = "sha256";
+/// The name of the algorithm that is used for [digest lines](crate::Settings::set_digest_line).
+#[cfg(not(feature = "sha2"))]
+pub(crate) const DIGEST_ALGORITHM: &str = "crc32";
+
+/// Returns the [`DIGEST_ALGORITHM`] digest of `bytes` as lowercase hexadecimal digits.
+pub(crate) fn digest(bytes: &[u8]) -> String {
+    #[cfg(feature = "sha2")]
+    {
+        use sha2::Digest;
+        sha2::Sha256::digest(bytes).iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+    #[cfg(not(feature = "sha2"))]
+    {
+        let mut checksum = Checksum::new(ChecksumKind::Crc32);
+        checksum.update(bytes);
+        format!("{:08x}", checksum.finish())
+    }
+}
+
+/// Tests for the checksum algorithms.
+#[cfg(any(test, feature = "doc_tests"))]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_checksum_crc32() {
+        let mut checksum = Checksum::new(ChecksumKind::Crc32);
+        checksum.update(b"123456789");
+        assert_eq!(checksum.finish(), 0xCBF43926);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_checksum_crc32_incremental() {
+        let mut checksum = Checksum::new(ChecksumKind::Crc32);
+        checksum.update(b"1234");
+        checksum.update(b"56789");
+        assert_eq!(checksum.finish(), 0xCBF43926);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_checksum_adler32() {
+        let mut checksum = Checksum::new(ChecksumKind::Adler32);
+        checksum.update(b"Wikipedia");
+        assert_eq!(checksum.finish(), 0x11E60398);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_checksum_reverse_bits() {
+        let reversed: Vec<u8> = b"123456789".iter().map(|byte| byte.reverse_bits()).collect();
+        let mut checksum = Checksum::new(ChecksumKind::Crc32);
+        checksum.set_reverse_bits(true);
+        checksum.update(b"garbage");
+        checksum.reset();
+        checksum.update(&reversed);
+        assert_eq!(checksum.finish(), 0xCBF43926);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_checksum_empty() {
+        assert_eq!(Checksum::new(ChecksumKind::Crc32).finish(), 0);
+        assert_eq!(Checksum::new(ChecksumKind::Adler32).finish(), 1);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_digest() {
+        #[cfg(feature = "sha2")]
+        assert_eq!(digest(b"abc"),
+                   "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        #[cfg(not(feature = "sha2"))]
+        assert_eq!(digest(b"123456789"), "cbf43926");
+    }
+}
@@ -0,0 +1,201 @@
+/* Copyright (C) 2022  Fabian Moos
+ * This file is part of encodex.
+ *
+ * encodex is free software: you can redistribute it and/or modify it under the terms of the GNU
+ * General Public License as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * encodex is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without
+ * even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+ * General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along with encodex. If not,
+ * see <https://www.gnu.org/licenses/>.
+ */
+
+//! Incremental decoding of [`Base64`](crate::Base::Base64) data.
+
+use crate::error::EncodeError;
+use crate::settings::{Base, Settings};
+
+/// The number of characters of a [`Base64`](crate::Base::Base64) group.
+const GROUP_LEN: usize = 4;
+
+/// Decodes [`Base64`](crate::Base::Base64) or [`Base64url`](crate::Base::Base64url) data that
+/// arrives piece by piece, with an explicit end of the stream.
+///
+/// The decoder is a small state machine over the characters of the current group:
+///
+/// - **Empty**: no characters are buffered. This is the start state and the state after every
+///   complete group.
+/// - **Partial**: 1 to 3 characters of a group are buffered. [`update`](Base64Decoder::update)
+///   moves to this state when its input ends in the middle of a group and back to *Empty* as
+///   soon as the group is completed and decoded.
+///
+/// [`finalize`](Base64Decoder::finalize) ends the stream. In the *Empty* state it succeeds
+/// without output. In the *Partial* state it depends on the mode:
+///
+/// - **Padded**: the last group must have been completed with padding, so any buffered
+///   characters fail with [`EncodeError::IncompleteGroup`] and the number of characters the
+///   group needed.
+/// - **Unpadded**: 2 or 3 buffered characters are the shortened last group and are decoded as
+///   if they were padded. A single character can't hold a whole byte and fails with
+///   [`EncodeError::IncompleteGroup`].
+///
+/// Complete groups are decoded like a [`TranslationUnit`](crate::TranslationUnit) with the
+/// default [`Settings`](crate::Settings) decodes them, so padding may also end groups in the
+/// middle of the stream in both modes and the input must not contain line breaks. The positions
+/// of decoding errors refer to the whole input of all calls. After an error the decoder should
+/// be discarded. Only available with the `base64` feature.
+///
+/// # Usage Example
+///
+/// ```
+/// use encodex::{Base, Base64Decoder, EncodeError};
+///
+/// let mut decoder = Base64Decoder::new(Base::Base64, false).unwrap();
+/// let mut output = decoder.update(b"Zm9vY").unwrap();
+/// output.extend(decoder.update(b"mE").unwrap());
+/// output.extend(decoder.finalize().unwrap());
+/// assert_eq!(output, b"fooba");
+///
+/// let mut decoder = Base64Decoder::new(Base::Base64, true).unwrap();
+/// assert_eq!(decoder.update(b"Zm9vYmE"), Ok(b"foo".to_vec()));
+/// assert_eq!(decoder.finalize(), Err(EncodeError::IncompleteGroup { got: 3, needed: 4 }));
+/// ```
+pub struct Base64Decoder {
+    base: Base,
+    padded: bool,
+    /// The characters of the current, incomplete group.
+    partial_group: Vec<u8>,
+    /// The number of input characters that have been decoded so far.
+    offset: usize,
+}
+
+impl Base64Decoder {
+    /// Creates a [`Base64Decoder`] for data encoded as `base`, which requires a padded last
+    /// group if `padded` is set.
+    ///
+    /// Fails with [`EncodeError::UnsupportedBase`](crate::EncodeError::UnsupportedBase) for
+    /// all bases but [`Base64`](crate::Base::Base64) and [`Base64url`](crate::Base::Base64url).
+    pub fn new(base: Base, padded: bool) -> Result<Base64Decoder, EncodeError> {
+        match base {
+            Base::Base64 | Base::Base64url => {
+                Ok(Base64Decoder { base, padded, partial_group: Vec::new(), offset: 0 })
+            }
+            base => { Err(EncodeError::UnsupportedBase(base)) }
+        }
+    }
+
+    /// Returns the [`Base`](crate::Base) the input is decoded as.
+    pub fn base(&self) -> Base { self.base }
+
+    /// Returns whether the last group must be padded.
+    pub fn padded(&self) -> bool { self.padded }
+
+    /// Adds `input` to the current group and returns the bytes of all groups that are complete
+    /// now. The characters of an incomplete group are buffered until the next call.
+    pub fn update(&mut self, input: &[u8]) -> Result<Vec<u8>, EncodeError> {
+        self.partial_group.extend_from_slice(input);
+        let decode_len = self.partial_group.len() / GROUP_LEN * GROUP_LEN;
+        let decoded_data = self.decode(decode_len, decode_len)?;
+        self.partial_group.drain(..decode_len);
+        self.offset += decode_len;
+        Ok(decoded_data)
+    }
+
+    /// Ends the stream and returns the bytes of the buffered characters, see the
+    /// [state machine](Base64Decoder) for when this fails.
+    pub fn finalize(self) -> Result<Vec<u8>, EncodeError> {
+        let got = self.partial_group.len();
+        match got {
+            0 => { Ok(Vec::new()) }
+            _ if self.padded => { Err(EncodeError::IncompleteGroup { got, needed: GROUP_LEN }) }
+            1 => { Err(EncodeError::IncompleteGroup { got, needed: 2 }) }
+            _ => { self.decode(got, GROUP_LEN) }
+        }
+    }
+
+    /// Decodes the first `len` buffered characters, padded to `padded_len` characters.
+    fn decode(&self, len: usize, padded_len: usize) -> Result<Vec<u8>, EncodeError> {
+        if len == 0 { return Ok(Vec::new()); }
+        let mut encoded_data = self.partial_group[..len].to_vec();
+        encoded_data.resize(padded_len, b'=');
+        self.base.decode_with(&encoded_data, &Settings::new())
+                 .map_err(|error| error.offset_position(self.offset))
+    }
+}
+
+/// Tests for the incremental decoding.
+#[cfg(any(test, feature = "doc_tests"))]
+mod tests {
+    use super::*;
+    use crate::base_encoding::encode_to_string;
+
+    /// Feeds `input` to a new [`Base64Decoder`] in pieces of the given lengths, which are
+    /// repeated until the input ends.
+    fn decode_in_pieces(input: &[u8], padded: bool, piece_lens: &[usize])
+                        -> Result<Vec<u8>, EncodeError> {
+        let mut decoder = Base64Decoder::new(Base::Base64, padded)?;
+        let mut output = Vec::new();
+        let mut rest = input;
+        for &piece_len in piece_lens.iter().cycle() {
+            if rest.is_empty() { break; }
+            let (piece, next) = rest.split_at(piece_len.min(rest.len()));
+            output.extend(decoder.update(piece)?);
+            rest = next;
+        }
+        output.extend(decoder.finalize()?);
+        Ok(output)
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base64_decoder_matches_batch_decoder() {
+        let piece_lens: [&[usize]; 5] = [&[1], &[3, 1], &[2, 5], &[4], &[7, 1, 1]];
+        for len in 0..40 {
+            let data: Vec<u8> = (0..len).map(|index| (index * 37) as u8).collect();
+            let padded = encode_to_string(&data, Base::Base64).unwrap();
+            let unpadded = padded.trim_end_matches('=');
+            let batch = Base::Base64.decode_with(padded.as_bytes(), &Settings::new());
+            assert_eq!(batch, Ok(data.clone()));
+            for lens in piece_lens {
+                assert_eq!(decode_in_pieces(padded.as_bytes(), true, lens), batch);
+                assert_eq!(decode_in_pieces(padded.as_bytes(), false, lens), batch);
+                assert_eq!(decode_in_pieces(unpadded.as_bytes(), false, lens), batch);
+            }
+        }
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base64_decoder_finalize() {
+        let decoder = Base64Decoder::new(Base::Base64url, true).unwrap();
+        assert_eq!(decoder.finalize(), Ok(Vec::new()));
+
+        let mut decoder = Base64Decoder::new(Base::Base64, true).unwrap();
+        assert_eq!(decoder.update(b"Zm9vYm"), Ok(b"foo".to_vec()));
+        assert_eq!(decoder.finalize(), Err(EncodeError::IncompleteGroup { got: 2, needed: 4 }));
+
+        let mut decoder = Base64Decoder::new(Base::Base64, false).unwrap();
+        assert_eq!(decoder.update(b"Zm9vYm"), Ok(b"foo".to_vec()));
+        assert_eq!(decoder.finalize(), Ok(b"b".to_vec()));
+
+        let mut decoder = Base64Decoder::new(Base::Base64, false).unwrap();
+        assert_eq!(decoder.update(b"Zm9vY"), Ok(b"foo".to_vec()));
+        assert_eq!(decoder.finalize(), Err(EncodeError::IncompleteGroup { got: 1, needed: 2 }));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base64_decoder_errors() {
+        assert!(Base64Decoder::new(Base::Base32, false).is_err());
+        assert_eq!(decode_in_pieces(b"Zm9vYmFyZm!v", true, &[3]),
+                   Err(EncodeError::InvalidCharacter { base: Base::Base64,
+                                                       character: b'!',
+                                                       position: 10 }));
+        let mut decoder = Base64Decoder::new(Base::Base64, false).unwrap();
+        assert_eq!(decoder.update(b"Zm9vY!"), Ok(b"foo".to_vec()));
+        assert_eq!(decoder.finalize(),
+                   Err(EncodeError::InvalidCharacter { base: Base::Base64,
+                                                       character: b'!',
+                                                       position: 5 }));
+    }
+}
@@ -0,0 +1,123 @@
+/* Copyright (C) 2022  Fabian Moos
+ * This file is part of encodex.
+ *
+ * encodex is free software: you can redistribute it and/or modify it under the terms of the GNU
+ * General Public License as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * encodex is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without
+ * even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+ * General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along with encodex. If not,
+ * see <https://www.gnu.org/licenses/>.
+ */
+
+
+//! The interface between a [`TranslationUnit`](crate::TranslationUnit) and an encoding.
+
+use crate::base_encoding::encode_to_string;
+use crate::error::EncodeError;
+use crate::settings::{Base, Settings};
+
+/// An encoding that a [`TranslationUnit`](crate::TranslationUnit) can en- and decode data with.
+///
+/// Implement it to use an encoding that is not one of the built-in [`Base`](crate::Base)s with
+/// [`TranslationUnit::with_encoding`](crate::TranslationUnit::with_encoding). The unit still
+/// applies all options that don't depend on the base, like line breaks, ignored characters or
+/// digest lines, around the calls of the encoding. Every [`Base`](crate::Base) implements the
+/// trait with its default [`Settings`](crate::Settings).
+///
+/// An encoding that reports its [group length](Encoding::group_len) can also be converted piece
+/// by piece with [`Transcoder::with_encodings`](crate::Transcoder::with_encodings) and decoded
+/// block by block with [`decode_streaming_with`](crate::decode_streaming_with).
+///
+/// The trait only covers the en- and decoding of the data, so it has some limits:
+///
+/// - The options that depend on the alphabet of the base, like
+///   [lowercase output](crate::Settings::set_lowercase) or a
+///   [checksum suffix](crate::Settings::set_checksum_suffix), still use the base of the
+///   [`Settings`](crate::Settings) of the unit.
+/// - A unit without a custom encoding doesn't call the trait, because the built-in bases also
+///   depend on options like the [Bech32 prefix](crate::Settings::set_bech32_hrp) that are not
+///   part of it.
+/// - [`Base64Decoder`](crate::Base64Decoder),
+///   [`Base::decode_streaming`](crate::Base::decode_streaming), the functions that take a
+///   [`Base`](crate::Base) and the command line tool only support the built-in bases.
+///
+/// # Usage Example
+///
+/// ```
+/// use encodex::{EncodeError, Encoding};
+///
+/// /// Encodes every byte as itself.
+/// struct Identity;
+///
+/// impl Encoding for Identity {
+///     fn encode(&self, data: &[u8]) -> Result<Vec<u8>, EncodeError> { Ok(data.to_vec()) }
+///     fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, EncodeError> { Ok(encoded.to_vec()) }
+/// }
+///
+/// assert_eq!(Identity.encode(b"foo"), Ok(b"foo".to_vec()));
+/// assert_eq!(encodex::Base::Base64.encode(b"foo"), Ok(b"Zm9v".to_vec()));
+/// ```
+pub trait Encoding {
+    /// Encodes `data` and returns the encoded characters.
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>, EncodeError>;
+
+    /// Decodes the `encoded` characters and returns the decoded bytes.
+    fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, EncodeError>;
+
+    /// Returns the number of bytes in a group and the number of characters the group is encoded
+    /// as, if every group is en- and decoded independently of the others.
+    ///
+    /// Only the last group may be shorter. The default returns [`None`], so the data is only en-
+    /// and decoded as a whole.
+    fn group_len(&self) -> Option<(usize, usize)> { None }
+}
+
+impl Encoding for Base {
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>, EncodeError> {
+        encode_to_string(data, *self).map(String::into_bytes)
+    }
+
+    fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, EncodeError> {
+        self.decode_with(encoded, &Settings::new())
+    }
+
+    fn group_len(&self) -> Option<(usize, usize)> {
+        match self {
+            // The data of these bases depends on all of its bytes.
+            Base::ImapUtf7 | Base::Crockford32 | Base::Bech32 | Base::Guess => { None }
+            base => { Some((base.chunk_size(), base.encoded_chunk_size())) }
+        }
+    }
+}
+
+/// Tests for the [`Encoding`] trait.
+#[cfg(any(test, feature = "doc_tests"))]
+mod tests {
+    use super::*;
+
+    #[cfg(all(feature = "base64", feature = "base32", feature = "base16", feature = "base45"))]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_encoding_base() {
+        assert_eq!(Base::Base64.encode(b"foobar"), Ok(b"Zm9vYmFy".to_vec()));
+        assert_eq!(Base::Base32.decode(b"MZXW6==="), Ok(b"foo".to_vec()));
+        assert_eq!(Base::Guess.encode(b"foo"), Err(EncodeError::UnsupportedBase(Base::Guess)));
+        let encodings: [Box<dyn Encoding>; 2] = [Box::new(Base::Base16), Box::new(Base::Base45)];
+        for encoding in encodings {
+            assert_eq!(encoding.decode(&encoding.encode(b"foo").unwrap()), Ok(b"foo".to_vec()));
+        }
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_encoding_base_group_len() {
+        assert_eq!(Base::Base64.group_len(), Some((3, 4)));
+        assert_eq!(Base::Base32hex.group_len(), Some((5, 8)));
+        assert_eq!(Base::Base16.group_len(), Some((1, 2)));
+        assert_eq!(Base::Base45.group_len(), Some((2, 3)));
+        assert_eq!(Base::Bech32.group_len(), None);
+        assert_eq!(Base::Guess.group_len(), None);
+    }
+}
@@ -0,0 +1,418 @@
+/* Copyright (C) 2022  Fabian Moos
+ * This file is part of encodex.
+ *
+ * encodex is free software: you can redistribute it and/or modify it under the terms of the GNU
+ * General Public License as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * encodex is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without
+ * even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+ * General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along with encodex. If not,
+ * see <https://www.gnu.org/licenses/>.
+ */
+
+
+//! Errors that can occur while en-/decoding data.
+
+use std::{error, fmt, io, path};
+
+use crate::settings::Base;
+
+/// Describes why a [`TranslationUnit`](crate::TranslationUnit) could not translate its data.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EncodeError {
+    /// The [`Base`](crate::Base) can't be used for the requested operation.
+    UnsupportedBase(Base),
+    /// A custom [`Encoding`](crate::Encoding) can't be used for the requested operation, because
+    /// it doesn't en- and decode its data [group by group](crate::Encoding::group_len).
+    UngroupedEncoding,
+    /// The number of encoded characters can't encode a whole number of bytes, or is too small to
+    /// hold the checksum of the encoding.
+    InvalidLength {
+        /// The base the data was decoded as.
+        base: Base,
+        /// The number of encoded characters.
+        length: usize,
+    },
+    /// The encoded data ends in the middle of a group, which is common for truncated data.
+    IncompleteGroup {
+        /// The number of characters in the incomplete final group.
+        got: usize,
+        /// The number of characters that are needed to complete the group.
+        needed: usize,
+    },
+    /// The encoded data contains a character that is not part of the alphabet.
+    InvalidCharacter {
+        /// The base the data was decoded as.
+        base: Base,
+        /// The offending character.
+        character: u8,
+        /// The index of the offending character in the encoded data.
+        position: usize,
+    },
+    /// A data character follows a padding character.
+    MisplacedPadding {
+        /// The base the data was decoded as.
+        base: Base,
+        /// The index of the data character that follows the padding.
+        position: usize,
+    },
+    /// The unused bits of the last data character are not zero, so the data is not encoded
+    /// canonically. Only reported by [strict](crate::Settings::set_strict) decoding.
+    NonCanonicalEncoding {
+        /// The base the data was decoded as.
+        base: Base,
+        /// The index of the last data character in the encoded data.
+        position: usize,
+    },
+    /// The number of padding characters in a group does not correspond to a number of bytes.
+    InvalidPadding {
+        /// The base the data was decoded as.
+        base: Base,
+    },
+    /// The check symbol of [`Crockford32`](crate::Base::Crockford32) encoded data doesn't match
+    /// the decoded data.
+    InvalidCheckSymbol {
+        /// The base the data was decoded as.
+        base: Base,
+        /// The offending check symbol.
+        character: u8,
+        /// The index of the check symbol in the encoded data.
+        position: usize,
+    },
+    /// A group of [`Base45`](crate::Base::Base45) encoded characters encodes a number that
+    /// doesn't fit into the bytes of the group.
+    InvalidGroupValue {
+        /// The base the data was decoded as.
+        base: Base,
+        /// The index of the first character of the group in the encoded data.
+        position: usize,
+    },
+    /// The checksum of [`Bech32`](crate::Base::Bech32) encoded data doesn't match the data.
+    InvalidChecksum {
+        /// The base the data was decoded as.
+        base: Base,
+    },
+    /// The human-readable part of [`Bech32`](crate::Base::Bech32) data is empty, too long,
+    /// contains characters outside of the range 33 to 126 of US-ASCII, is not followed by the
+    /// separator `1` or doesn't match the configured one.
+    InvalidHrp,
+    /// The encoded data mixes upper- and lowercase letters, although the encoding only allows
+    /// one of them.
+    MixedCase {
+        /// The base the data was decoded as.
+        base: Base,
+    },
+    /// The text to encode is not valid `UTF-8`.
+    InvalidUtf8 {
+        /// The index of the first byte that is not part of a valid `UTF-8` sequence.
+        position: usize,
+    },
+    /// A shift sequence of the [`ImapUtf7`](crate::Base::ImapUtf7) encoding is not terminated or
+    /// doesn't contain valid `UTF-16`.
+    InvalidShiftSequence {
+        /// The index of the `&` that starts the shift sequence.
+        position: usize,
+    },
+    /// The [JSON string](crate::Settings::set_json_unescape) to decode has a quote at only one of
+    /// its ends.
+    UnmatchedQuote {
+        /// The index of the unmatched quote.
+        position: usize,
+    },
+    /// The input is longer than the configured
+    /// [maximum input length](crate::Settings::set_max_input_len) or than the encoding allows,
+    /// like the 90 characters of [`Bech32`](crate::Base::Bech32).
+    InputTooLarge {
+        /// The length of the input.
+        length: usize,
+        /// The maximum length of the input.
+        limit: usize,
+    },
+    /// The name of a [`Base`](crate::Base) could not be recognized.
+    UnknownBase(String),
+    /// The name of an [`EncodeMode`](crate::EncodeMode) could not be recognized.
+    UnknownEncodeMode(String),
+    /// A [settings specification](crate::Settings::from_spec) is not of the form
+    /// `<base>/<mode>`.
+    InvalidSpec(String),
+    /// The number of decoded bytes differs from the expected number.
+    LengthMismatch {
+        /// The expected number of bytes.
+        expected: usize,
+        /// The actual number of bytes.
+        got: usize,
+    },
+    /// The sign character of a [signed integer](crate::encode_i64) is part of the alphabet or
+    /// the padding of the base, so it can't be told apart from the encoded magnitude.
+    InvalidSign {
+        /// The base the integer is encoded as.
+        base: Base,
+        /// The offending sign character.
+        sign: char,
+    },
+    /// A decoded [signed integer](crate::decode_i64) doesn't fit into an [`i64`].
+    IntegerOverflow,
+    /// A [data URI](crate::from_data_uri) doesn't start with `data:` or its payload is not
+    /// marked as `;base64`.
+    InvalidDataUri,
+    /// The input of [`decode_tagged`](crate::decode_tagged) doesn't start with a
+    /// `<base>:<length>:` header.
+    InvalidTaggedHeader,
+    /// The encoded data doesn't end with a valid [digest line](crate::Settings::set_digest_line).
+    InvalidDigestLine,
+    /// The digest of the decoded data differs from the one in the
+    /// [digest line](crate::Settings::set_digest_line).
+    DigestMismatch {
+        /// The digest in the digest line.
+        expected: String,
+        /// The digest of the decoded data.
+        got: String,
+    },
+    /// None of the bases tried by [`decode_best_effort`](crate::decode_best_effort) could decode
+    /// the input.
+    NoMatchingBase {
+        /// Every base that has been tried together with the reason it failed, in the order they
+        /// have been tried.
+        failures: Vec<(Base, EncodeError)>,
+    },
+    /// The data given to [`encode_records`](crate::Base::encode_records) can't be split into
+    /// records of the given size.
+    IncompleteRecord {
+        /// The number of bytes of the data.
+        length: usize,
+        /// The number of bytes per record.
+        record_size: usize,
+    },
+    /// The [checksum suffix](crate::Settings::set_checksum_suffix) of the encoded data is missing
+    /// or doesn't match.
+    InvalidChecksumSuffix {
+        /// The base the data is encoded as.
+        base: Base,
+    },
+    /// A file could not be read by [`decode_file`](crate::decode_file) or
+    /// [`encode_file`](crate::encode_file).
+    Io {
+        /// The path of the file.
+        path: path::PathBuf,
+        /// The kind of the underlying I/O error.
+        kind: io::ErrorKind,
+        /// The message of the underlying I/O error.
+        message: String,
+    },
+    /// A decoding error together with the encoded data around its position, if
+    /// [error context](crate::Settings::set_error_context) is enabled.
+    WithContext {
+        /// The error that occurred.
+        error: Box<EncodeError>,
+        /// Up to 16 bytes around the position of the error, non-printable bytes are escaped.
+        context: String,
+    },
+}
+
+impl EncodeError {
+    /// Returns the index of the character in the encoded data that caused the error, if the
+    /// error refers to a single position.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            EncodeError::InvalidCharacter { position, .. }
+            | EncodeError::MisplacedPadding { position, .. }
+            | EncodeError::InvalidCheckSymbol { position, .. }
+            | EncodeError::NonCanonicalEncoding { position, .. }
+            | EncodeError::InvalidGroupValue { position, .. }
+            | EncodeError::InvalidShiftSequence { position }
+            | EncodeError::UnmatchedQuote { position } => { Some(*position) }
+            EncodeError::WithContext { error, .. } => { error.position() }
+            _ => { None }
+        }
+    }
+
+    /// Moves the position of the error by `offset` characters. This is used when a part of a
+    /// larger input has been decoded on its own. Errors without a position are returned
+    /// unchanged.
+    pub fn offset_position(self, offset: usize) -> EncodeError {
+        match self {
+            EncodeError::InvalidCharacter { base, character, position } => {
+                EncodeError::InvalidCharacter { base, character, position: position + offset }
+            }
+            EncodeError::MisplacedPadding { base, position } => {
+                EncodeError::MisplacedPadding { base, position: position + offset }
+            }
+            EncodeError::InvalidCheckSymbol { base, character, position } => {
+                EncodeError::InvalidCheckSymbol { base, character, position: position + offset }
+            }
+            EncodeError::NonCanonicalEncoding { base, position } => {
+                EncodeError::NonCanonicalEncoding { base, position: position + offset }
+            }
+            EncodeError::InvalidGroupValue { base, position } => {
+                EncodeError::InvalidGroupValue { base, position: position + offset }
+            }
+            EncodeError::InvalidShiftSequence { position } => {
+                EncodeError::InvalidShiftSequence { position: position + offset }
+            }
+            EncodeError::UnmatchedQuote { position } => {
+                EncodeError::UnmatchedQuote { position: position + offset }
+            }
+            EncodeError::WithContext { error, context } => {
+                EncodeError::WithContext { error: Box::new(error.offset_position(offset)), context }
+            }
+            error => { error }
+        }
+    }
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::UnsupportedBase(base) => {
+                write!(f, "En-/Decoding as {} is not supported!", base)
+            }
+            EncodeError::UngroupedEncoding => {
+                write!(f, "The encoding can't en-/decode its data group by group!")
+            }
+            EncodeError::InvalidLength { base, length } => {
+                write!(f, "Invalid number of {} encoded characters ({})!", base, length)
+            }
+            EncodeError::IncompleteGroup { got, needed } => {
+                write!(f, "Incomplete final group of {} characters, {} are needed!", got, needed)
+            }
+            EncodeError::InvalidCharacter { base, character, position } => {
+                write!(f, "Non {}-alphabet character '{}' encountered at position {}!", base,
+                       char::from(*character).escape_default(), position)
+            }
+            EncodeError::MisplacedPadding { base, position } => {
+                write!(f, "Misplaced padding in {} encoded data at position {}!", base, position)
+            }
+            EncodeError::NonCanonicalEncoding { base, position } => {
+                write!(f, "Non canonical {} encoding, unused bits are set at position {}!", base,
+                       position)
+            }
+            EncodeError::InvalidPadding { base } => {
+                write!(f, "Invalid padding in {} encoded data!", base)
+            }
+            EncodeError::InvalidCheckSymbol { base, character, position } => {
+                write!(f, "Check symbol '{}' at position {} doesn't match the {} encoded data!",
+                       char::from(*character).escape_default(), position, base)
+            }
+            EncodeError::InvalidGroupValue { base, position } => {
+                write!(f, "Value of the {} encoded group at position {} is out of range!", base,
+                       position)
+            }
+            EncodeError::InvalidChecksum { base } => {
+                write!(f, "The checksum doesn't match the {} encoded data!", base)
+            }
+            EncodeError::InvalidHrp => {
+                write!(f, "Invalid or missing human-readable part!")
+            }
+            EncodeError::MixedCase { base } => {
+                write!(f, "{} encoded data mixes upper- and lowercase letters!", base)
+            }
+            EncodeError::InvalidUtf8 { position } => {
+                write!(f, "Invalid UTF-8 encountered at position {}!", position)
+            }
+            EncodeError::InvalidShiftSequence { position } => {
+                write!(f, "Invalid shift sequence starting at position {}!", position)
+            }
+            EncodeError::UnmatchedQuote { position } => {
+                write!(f, "Unmatched quote at position {}!", position)
+            }
+            EncodeError::InputTooLarge { length, limit } => {
+                write!(f, "Input of {} bytes exceeds the limit of {} bytes!", length, limit)
+            }
+            EncodeError::UnknownBase(name) => { write!(f, "Unrecognized base type '{}'!", name) }
+            EncodeError::UnknownEncodeMode(name) => {
+                write!(f, "Unrecognized encode mode '{}'!", name)
+            }
+            EncodeError::InvalidSpec(spec) => {
+                write!(f, "Invalid specification '{}', expected '<base>/<mode>'!", spec)
+            }
+            EncodeError::LengthMismatch { expected, got } => {
+                write!(f, "Expected {} decoded bytes, but got {}!", expected, got)
+            }
+            EncodeError::InvalidSign { base, sign } => {
+                write!(f, "The sign '{}' is part of the {} alphabet!", sign.escape_default(), base)
+            }
+            EncodeError::IntegerOverflow => {
+                write!(f, "The decoded integer doesn't fit into 64 bits!")
+            }
+            EncodeError::InvalidDataUri => {
+                write!(f, "Invalid data URI, expected 'data:<mime type>;base64,<data>'!")
+            }
+            EncodeError::InvalidTaggedHeader => {
+                write!(f, "Invalid tagged data, expected '<base>:<length>:<data>'!")
+            }
+            EncodeError::InvalidDigestLine => {
+                write!(f, "The encoded data doesn't end with a valid digest line!")
+            }
+            EncodeError::DigestMismatch { expected, got } => {
+                write!(f, "Digest mismatch, expected {} but the decoded data has {}!", expected,
+                       got)
+            }
+            EncodeError::NoMatchingBase { failures } => {
+                write!(f, "The input could not be decoded as any base!")?;
+                for (base, error) in failures { write!(f, "\n    {}: {}", base, error)?; }
+                Ok(())
+            }
+            EncodeError::IncompleteRecord { length, record_size } => {
+                write!(f, "{} bytes can't be split into records of {} bytes!", length, record_size)
+            }
+            EncodeError::InvalidChecksumSuffix { base } => {
+                write!(f, "The checksum suffix of the {} encoded data doesn't match!", base)
+            }
+            EncodeError::Io { path, message, .. } => {
+                write!(f, "Could not read the file '{}': {}!", path.display(), message)
+            }
+            EncodeError::WithContext { error, context } => {
+                write!(f, "{} Context: \"{}\"", error, context)
+            }
+        }
+    }
+}
+
+impl error::Error for EncodeError {}
+
+/// Converts the error for use with [`std::io`], e.g. in a [`Read`](std::io::Read)
+/// implementation. Errors in the configuration, like an unknown [`Base`](crate::Base), become
+/// [`InvalidInput`](io::ErrorKind::InvalidInput) errors, all others are caused by the data and
+/// become [`InvalidData`](io::ErrorKind::InvalidData) errors. [`Io`](EncodeError::Io) errors keep
+/// their original kind. The [`EncodeError`] is kept as the inner error.
+impl From<EncodeError> for io::Error {
+    fn from(error: EncodeError) -> io::Error {
+        let kind = match error {
+            EncodeError::UnsupportedBase(_)
+            | EncodeError::UngroupedEncoding
+            | EncodeError::UnknownBase(_)
+            | EncodeError::UnknownEncodeMode(_)
+            | EncodeError::InvalidSpec(_) => { io::ErrorKind::InvalidInput }
+            EncodeError::Io { kind, .. } => { kind }
+            _ => { io::ErrorKind::InvalidData }
+        };
+        io::Error::new(kind, error)
+    }
+}
+
+/// Tests for the conversion of errors.
+#[cfg(any(test, feature = "doc_tests"))]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_encode_error_into_io_error_invalid_data() {
+        let error = EncodeError::InvalidCharacter { base: Base::Base64,
+                                                    character: b'!',
+                                                    position: 3 };
+        let io_error = io::Error::from(error.clone());
+        assert_eq!(io_error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(io_error.to_string(), error.to_string());
+        let inner = io_error.into_inner().unwrap().downcast::<EncodeError>().unwrap();
+        assert_eq!(*inner, error);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_encode_error_into_io_error_invalid_input() {
+        let io_error: io::Error = EncodeError::UnsupportedBase(Base::Guess).into();
+        assert_eq!(io_error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
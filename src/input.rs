@@ -14,6 +14,11 @@
  */
 
 use std::{fs, io, path};
+use std::io::Read;
+
+/// The magic bytes at the start of gzip compressed data.
+#[cfg(feature = "flate2")]
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
 
 #[derive(Clone, Copy)]
 pub enum ReadMode {
@@ -23,20 +28,49 @@ pub enum ReadMode {
 
 pub struct Input {
     byte_streams: Vec<Vec<u8>>,
+    /// Whether the byte stream at the same index has been read from a file.
+    from_file: Vec<bool>,
+    /// The name of the source of the byte stream at the same index, if it has one.
+    labels: Vec<Option<String>>,
+    /// The label of the byte stream that has been returned last.
+    current_label: Option<String>,
     read_mode: ReadMode,
+    stdin: Option<Box<dyn Read>>,
+    stdin_block_len: usize,
+    /// Whether a trailing line break is removed from the last block of stdin.
+    strip_stdin_newline: bool,
 }
 
 impl Input {
     pub fn new() -> Input {
         Input {
             byte_streams: Vec::new(),
+            from_file: Vec::new(),
+            labels: Vec::new(),
+            current_label: None,
             read_mode: ReadMode::FileName,
+            stdin: None,
+            stdin_block_len: usize::MAX,
+            strip_stdin_newline: false,
         }
     }
 
-    pub fn add_file(&mut self, file_path: path::PathBuf) {
+    /// Reads the file at `file_path` as a byte stream that is labeled with `name`.
+    pub fn add_named_file(&mut self, name: String, file_path: path::PathBuf) {
         match fs::read(file_path.clone()) {
-            Ok(bytes) => { self.byte_streams.push(bytes); }
+            Ok(bytes) => {
+                #[cfg(feature = "flate2")]
+                let bytes = match decompress_gzip(bytes) {
+                    Ok(bytes) => { bytes }
+                    Err(_) => {
+                        eprintln!("Could not decompress file '{}'!", file_path.to_str().unwrap());
+                        return;
+                    }
+                };
+                self.byte_streams.push(bytes);
+                self.from_file.push(true);
+                self.labels.push(Some(name));
+            }
             Err(error) => {
                 match error.kind() {
                     io::ErrorKind::NotFound => {
@@ -57,8 +91,32 @@ impl Input {
 
     pub fn add_string_as_byte_stream(&mut self, string: String) {
         self.byte_streams.push(string.into_bytes());
+        self.from_file.push(false);
+        self.labels.push(None);
+    }
+
+    /// Removes a single line break (`\n` or `\r\n`) from the end of every byte stream that has
+    /// been read from a file and from the end of stdin. Almost every file that holds encoded data
+    /// ends with one, and so does the encoded output of a pipe.
+    pub fn strip_trailing_newlines(&mut self) {
+        for (bytes, &from_file) in self.byte_streams.iter_mut().zip(&self.from_file) {
+            if from_file { strip_trailing_newline(bytes); }
+        }
+        self.strip_stdin_newline = true;
+    }
+
+    /// Reads the input from `reader` after all other byte streams, in blocks of `block_len` bytes.
+    /// Only the last block may be shorter, `usize::MAX` reads everything as one block.
+    pub fn add_stdin(&mut self, reader: Box<dyn Read>, block_len: usize) {
+        self.stdin = Some(reader);
+        self.stdin_block_len = block_len;
     }
 
+    pub fn is_empty(&self) -> bool { self.byte_streams.is_empty() && self.stdin.is_none() }
+
+    /// Returns whether the byte streams are consecutive blocks of stdin.
+    pub fn reads_stdin(&self) -> bool { self.stdin.is_some() }
+
     pub fn read_mode(&self) -> ReadMode { self.read_mode }
 
     pub fn switch_read_mode(&mut self) {
@@ -68,6 +126,202 @@ impl Input {
         }
     }
 
-    pub fn get_next_byte_stream(&mut self) -> Option<Vec<u8>> { self.byte_streams.pop() }
+    pub fn concatenate_byte_streams(&mut self) {
+        let byte_stream = self.byte_streams.concat();
+        self.byte_streams = vec![byte_stream];
+        self.from_file = vec![false];
+        self.labels = vec![None];
+    }
+
+    /// Removes the labels of all byte streams, so their output isn't labeled.
+    pub fn clear_labels(&mut self) { self.labels.fill(None); }
+
+    /// Returns the label of the byte stream that [`get_next_byte_stream`] has returned last.
+    ///
+    /// [`get_next_byte_stream`]: Input::get_next_byte_stream
+    pub fn current_label(&self) -> Option<&str> { self.current_label.as_deref() }
+
+    pub fn get_next_byte_stream(&mut self) -> Option<Vec<u8>> {
+        if !self.byte_streams.is_empty() {
+            self.from_file.pop();
+            self.current_label = self.labels.pop().flatten();
+            return self.byte_streams.pop();
+        }
+        self.current_label = None;
+        let reader = self.stdin.as_mut()?;
+        let mut block = Vec::new();
+        let result = if self.stdin_block_len == usize::MAX {
+            reader.read_to_end(&mut block).map(|_| true)
+        } else {
+            read_block(reader, &mut block, self.stdin_block_len)
+        };
+        match result {
+            Ok(true) => {
+                self.stdin = None;
+                if self.strip_stdin_newline { strip_trailing_newline(&mut block); }
+            }
+            Ok(false) => {}
+            Err(error) => {
+                eprintln!("Could not read from stdin: {}!", error);
+                self.stdin = None;
+                return None;
+            }
+        }
+        if block.is_empty() { None } else { Some(block) }
+    }
+}
+
+/// Removes a single line break (`\n` or `\r\n`) from the end of `bytes`.
+fn strip_trailing_newline(bytes: &mut Vec<u8>) {
+    if bytes.ends_with(b"\r\n") {
+        bytes.truncate(bytes.len() - 2);
+    } else if bytes.ends_with(b"\n") {
+        bytes.pop();
+    }
+}
+
+/// Reads from `reader` until `block` holds `block_len` bytes or the end of the input is reached.
+/// Returns whether the end has been reached.
+fn read_block(reader: &mut dyn Read, block: &mut Vec<u8>, block_len: usize) -> io::Result<bool> {
+    block.resize(block_len, 0);
+    let mut filled = 0;
+    while filled < block_len {
+        match reader.read(&mut block[filled..]) {
+            Ok(0) => { break; }
+            Ok(count) => { filled += count; }
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => { return Err(error); }
+        }
+    }
+    block.truncate(filled);
+    Ok(filled < block_len)
+}
+
+/// Decompresses `bytes` if they start with the gzip magic bytes, otherwise returns them unchanged.
+#[cfg(feature = "flate2")]
+fn decompress_gzip(bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    if !bytes.starts_with(&GZIP_MAGIC) { return Ok(bytes); }
+    let mut decompressed = Vec::new();
+    flate2::read::GzDecoder::new(&bytes[..]).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
 }
 
+/// Tests for reading the input.
+#[cfg(any(test, feature = "doc_tests"))]
+mod tests {
+    use super::*;
+
+    fn fixture_path(name: &str) -> path::PathBuf {
+        let mut file_path = path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        file_path.push("tests/fixtures");
+        file_path.push(name);
+        file_path
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_input_add_file_plain() {
+        let mut input = Input::new();
+        input.add_named_file(String::from("foobar.txt"), fixture_path("foobar.txt"));
+        assert_eq!(input.get_next_byte_stream(), Some(b"foobar".to_vec()));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_input_strip_trailing_newlines() {
+        let mut input = Input::new();
+        input.add_named_file(String::from("foobar.b64"), fixture_path("foobar.b64"));
+        input.add_named_file(String::from("foobar.b32"), fixture_path("foobar.b32"));
+        input.add_named_file(String::from("foobar.txt"), fixture_path("foobar.txt"));
+        input.add_string_as_byte_stream(String::from("Zm9v\n"));
+        input.strip_trailing_newlines();
+        assert_eq!(input.get_next_byte_stream(), Some(b"Zm9v\n".to_vec()));
+        assert_eq!(input.get_next_byte_stream(), Some(b"foobar".to_vec()));
+        assert_eq!(input.get_next_byte_stream(), Some(b"MZXW6YTBOI======".to_vec()));
+        assert_eq!(input.get_next_byte_stream(), Some(b"Zm9vYmFy".to_vec()));
+    }
+
+    /// Returns at most two bytes per read, like a slow pipe.
+    struct Trickle(io::Cursor<Vec<u8>>);
+
+    impl Read for Trickle {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            let len = buffer.len().min(2);
+            self.0.read(&mut buffer[..len])
+        }
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_input_stdin_aligned_blocks() {
+        let mut input = Input::new();
+        input.add_stdin(Box::new(Trickle(io::Cursor::new(b"foobarbazq".to_vec()))), 3);
+        assert!(input.reads_stdin());
+        let mut blocks = Vec::new();
+        while let Some(block) = input.get_next_byte_stream() { blocks.push(block); }
+        assert_eq!(blocks, vec![b"foo".to_vec(), b"bar".to_vec(), b"baz".to_vec(), b"q".to_vec()]);
+        assert!(!input.reads_stdin());
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_input_stdin_whole() {
+        let mut input = Input::new();
+        input.add_stdin(Box::new(Trickle(io::Cursor::new(b"foobar".to_vec()))), usize::MAX);
+        assert_eq!(input.get_next_byte_stream(), Some(b"foobar".to_vec()));
+        assert_eq!(input.get_next_byte_stream(), None);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_input_stdin_strip_trailing_newline() {
+        let mut input = Input::new();
+        input.strip_trailing_newlines();
+        input.add_stdin(Box::new(Trickle(io::Cursor::new(b"Zm9vYg==\r\n".to_vec()))), 4);
+        assert_eq!(input.get_next_byte_stream(), Some(b"Zm9v".to_vec()));
+        assert_eq!(input.get_next_byte_stream(), Some(b"Yg==".to_vec()));
+        assert_eq!(input.get_next_byte_stream(), None);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_input_stdin_empty() {
+        let mut input = Input::new();
+        input.add_stdin(Box::new(io::empty()), 3);
+        assert_eq!(input.get_next_byte_stream(), None);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_input_labels() {
+        let mut input = Input::new();
+        input.add_named_file(String::from("first"), fixture_path("foobar.txt"));
+        input.add_string_as_byte_stream(String::from("foo"));
+        input.add_named_file(String::from("second"), fixture_path("foobar.b64"));
+        assert_eq!(input.current_label(), None);
+        assert!(input.get_next_byte_stream().is_some());
+        assert_eq!(input.current_label(), Some("second"));
+        assert!(input.get_next_byte_stream().is_some());
+        assert_eq!(input.current_label(), None);
+        assert!(input.get_next_byte_stream().is_some());
+        assert_eq!(input.current_label(), Some("first"));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_input_clear_labels() {
+        let mut input = Input::new();
+        input.add_named_file(String::from("first"), fixture_path("foobar.txt"));
+        input.clear_labels();
+        assert!(input.get_next_byte_stream().is_some());
+        assert_eq!(input.current_label(), None);
+    }
+
+    #[cfg(feature = "flate2")]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_input_add_file_gzip() {
+        let mut input = Input::new();
+        input.add_named_file(String::from("foobar.txt.gz"), fixture_path("foobar.txt.gz"));
+        assert_eq!(input.get_next_byte_stream(), Some(b"foobar".to_vec()));
+    }
+
+    #[cfg(feature = "flate2")]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_input_add_file_gzip_corrupt() {
+        let mut input = Input::new();
+        input.add_named_file(String::from("corrupt.gz"), fixture_path("corrupt.gz"));
+        assert_eq!(input.get_next_byte_stream(), None);
+    }
+}
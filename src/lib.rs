@@ -34,8 +34,30 @@
 //! ```
 
 mod base_encoding;
+mod checksum;
+#[cfg(feature = "base64")]
+mod decoder;
+mod encoding;
+mod error;
+#[cfg(feature = "macros")]
+mod macros;
 mod settings;
+mod transcoder;
 
-pub use base_encoding::TranslationUnit;
-pub use settings::{Base, EncodeMode, Settings};
+pub use base_encoding::{assert_encodes, convert_base64_variant, decode_best_effort, decode_bech32,
+                        decode_file, decode_i64, decode_streaming_with, decode_tagged, decode_u64,
+                        encode_bech32, encode_file, encode_i64, encode_shortest, encode_tagged,
+                        encode_u64, equivalent, transcode, DecodeReport, LintWarning,
+                        TranslationUnit};
+#[cfg(feature = "base64")]
+pub use base_encoding::{from_data_uri, to_data_uri};
+#[cfg(feature = "rand")]
+pub use base_encoding::random_token;
+pub use checksum::ChecksumKind;
+#[cfg(feature = "base64")]
+pub use decoder::Base64Decoder;
+pub use encoding::Encoding;
+pub use error::EncodeError;
+pub use settings::{Base, BitOrder, ByteOrder, CaseFold, EncodeMode, Settings};
+pub use transcoder::Transcoder;
 
@@ -0,0 +1,112 @@
+/* Copyright (C) 2022  Fabian Moos
+ * This file is part of encodex.
+ *
+ * encodex is free software: you can redistribute it and/or modify it under the terms of the GNU
+ * General Public License as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * encodex is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without
+ * even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+ * General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along with encodex. If not,
+ * see <https://www.gnu.org/licenses/>.
+ */
+
+//! Macros that generate en- and decoding helpers for user-defined types.
+
+/// Generates methods that en- and decode the byte fields of a struct as a
+/// [`Base`](crate::Base).
+///
+/// For every listed field of type [`Vec<u8>`] a pair of methods with the given names is added:
+/// the first one returns the encoded field as a [`String`], the second one decodes its argument
+/// into the field and leaves the field unchanged if that fails. All fields of one invocation use
+/// the same [`Base`](crate::Base) with its default [`Settings`](crate::Settings). The macro is
+/// only available with the `macros` feature.
+///
+/// # Usage Example
+///
+/// ```
+/// struct Credentials {
+///     key: Vec<u8>,
+///     nonce: Vec<u8>,
+/// }
+///
+/// encodex::base_field! {
+///     impl Credentials as Base64 {
+///         key: encode_key, decode_key;
+///         nonce: encode_nonce, decode_nonce;
+///     }
+/// }
+///
+/// let mut credentials = Credentials { key: b"foo".to_vec(), nonce: b"bar".to_vec() };
+/// assert_eq!(credentials.encode_key(), Ok(String::from("Zm9v")));
+/// assert_eq!(credentials.decode_nonce("YmF6"), Ok(()));
+/// assert_eq!(credentials.nonce, b"baz");
+/// ```
+#[macro_export]
+macro_rules! base_field {
+    (impl $type:ty as $base:ident { $($field:ident: $encode:ident, $decode:ident;)* }) => {
+        impl $type {
+            $(
+                #[doc = concat!("Encodes `", stringify!($field), "` as ", stringify!($base),
+                                ".")]
+                pub fn $encode(&self) -> Result<String, $crate::EncodeError> {
+                    let encoded = <$crate::Base as $crate::Encoding>::encode(
+                        &$crate::Base::$base, &self.$field)?;
+                    // Every alphabet consists of ASCII characters only.
+                    Ok(String::from_utf8(encoded).unwrap())
+                }
+
+                #[doc = concat!("Decodes `encoded` as ", stringify!($base), " into `",
+                                stringify!($field), "`.")]
+                pub fn $decode(&mut self, encoded: &str) -> Result<(), $crate::EncodeError> {
+                    self.$field = <$crate::Base as $crate::Encoding>::decode(
+                        &$crate::Base::$base, encoded.as_bytes())?;
+                    Ok(())
+                }
+            )*
+        }
+    };
+}
+
+/// Tests for the generated helpers.
+#[cfg(all(any(test, feature = "doc_tests"), feature = "base64"))]
+mod tests {
+    use crate::{Base, EncodeError};
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Message {
+        key: Vec<u8>,
+        payload: Vec<u8>,
+    }
+
+    base_field! {
+        impl Message as Base64 {
+            key: encode_key, decode_key;
+            payload: encode_payload, decode_payload;
+        }
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_field_round_trip() {
+        let message = Message { key: b"foobar".to_vec(), payload: (0..=255).collect() };
+        let encoded_key = message.encode_key().unwrap();
+        let encoded_payload = message.encode_payload().unwrap();
+        assert_eq!(encoded_key, "Zm9vYmFy");
+
+        let mut decoded = Message::default();
+        assert_eq!(decoded.decode_key(&encoded_key), Ok(()));
+        assert_eq!(decoded.decode_payload(&encoded_payload), Ok(()));
+        assert_eq!(decoded, message);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_field_decode_error() {
+        let mut message = Message { key: b"foo".to_vec(), payload: Vec::new() };
+        assert_eq!(message.decode_key("Zm9!"),
+                   Err(EncodeError::InvalidCharacter { base: Base::Base64, character: b'!',
+                                                       position: 3 }));
+        assert_eq!(message.key, b"foo");
+    }
+}
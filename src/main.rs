@@ -15,39 +15,110 @@
 
 mod args;
 mod input;
+mod output;
 
+use std::env;
+use std::io::{self, Write};
+use std::path::PathBuf;
 use std::process;
 
 use encodex::{EncodeMode, TranslationUnit};
 
+use crate::args::ErrorPolicy;
+use crate::output::OutputFormat;
+
 fn main() {
     let result = crate::args::parse_terminal_args();
-    let (mut input, config) = match result {
-        Ok((input, config)) => { (input, config) }
+    let (mut input, config, output_format, error_policy) = match result {
+        Ok(parsed_args) => { parsed_args }
         Err(error_message) => {
             eprintln!("{}", error_message);
             process::exit(1);
         }
     };
 
-    let mut data = input.get_next_byte_stream();
-    while data != None {
-        let bytes = data.unwrap();
-        let mut translation_unit = TranslationUnit::new(bytes, config);
-        if let Err(error_message) = translation_unit.translate() {
-            eprintln!("{}", error_message);
-            process::exit(1);
+    // The output of every byte stream is written as soon as it is translated, so only one of
+    // them is held in memory at a time. Consecutive blocks of stdin form a single output.
+    let mut stdout = io::BufWriter::new(io::stdout().lock());
+    let continuous = input.reads_stdin();
+    let mut split_writer = match output_format {
+        OutputFormat::Split(max_len) => {
+            let working_dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            Some(output::SplitWriter::new(&working_dir, max_len))
         }
-        match config.encode_mode() {
-            EncodeMode::Decode => { println!("{}", std::str::from_utf8(&translation_unit
-                                                   .get_decoded_data().as_ref().unwrap())
-                                                   .unwrap()); }
-            EncodeMode::Encode => { println!("{}", std::str::from_utf8(&translation_unit
-                                                   .get_encoded_data().as_ref().unwrap())
-                                                   .unwrap()); }
+        OutputFormat::Raw | OutputFormat::Hexdump | OutputFormat::Check => { None }
+    };
+    let mut failed = false;
+    // The error positions and the offsets of the hex dump continue across the blocks of stdin.
+    let mut input_offset = 0;
+    let mut output_offset = 0;
+    while let Some(bytes) = input.get_next_byte_stream() {
+        let block_start = input_offset;
+        if continuous { input_offset += bytes.len(); }
+        let mut translation_unit = TranslationUnit::new(bytes, config.clone());
+        if let Err(error) = translation_unit.translate() {
+            // Don't lose the output of the previous byte streams.
+            exit_on_write_error(stdout.flush());
+            eprintln!("{}", error.offset_position(block_start));
+            match error_policy {
+                // Skipping a block of stdin would leave an unnoticed gap in the single output.
+                ErrorPolicy::KeepGoing if !continuous => {
+                    failed = true;
+                    continue;
+                }
+                ErrorPolicy::KeepGoing | ErrorPolicy::FailFast => { process::exit(1); }
+            }
         }
-
-        data = input.get_next_byte_stream();
+        let output = match config.encode_mode() {
+            EncodeMode::Decode => { translation_unit.into_decoded() }
+            EncodeMode::Encode => { translation_unit.into_encoded() }
+        };
+        let output = output.unwrap();
+        if let Some(label) = input.current_label() {
+            if output_format != OutputFormat::Check {
+                exit_on_write_error(writeln!(stdout, "# {}", label));
+            }
+        }
+        match output_format {
+            OutputFormat::Raw => {
+                exit_on_write_error(stdout.write_all(&output));
+                if !continuous { exit_on_write_error(stdout.write_all(b"\n")); }
+            }
+            // Every line of the hex dump already ends with a line break.
+            OutputFormat::Hexdump => {
+                let dump = output::hexdump(&output, output_offset);
+                exit_on_write_error(stdout.write_all(dump.as_bytes()));
+                if continuous { output_offset += output.len(); }
+            }
+            // The input has been decoded without errors, that is all to check.
+            OutputFormat::Check => {}
+            // The names of the written files are printed instead of the output.
+            OutputFormat::Split(_) => {
+                let paths = split_writer.as_mut().unwrap().write(&output);
+                let paths = paths.unwrap_or_else(|error| {
+                    exit_on_write_error(stdout.flush());
+                    eprintln!("Could not write the split output: {}!", error);
+                    process::exit(1);
+                });
+                for path in paths {
+                    exit_on_write_error(writeln!(stdout, "{}", path.display()));
+                }
+            }
+        }
+    }
+    // Decoded data is written as it is, so the output of `encodex -d` matches the original file.
+    if continuous && output_format == OutputFormat::Raw
+       && config.encode_mode() == EncodeMode::Encode {
+        exit_on_write_error(stdout.write_all(b"\n"));
     }
+    exit_on_write_error(stdout.flush());
+    if failed { process::exit(1); }
 }
 
+/// Exits the program if the output could not be written, e.g. because stdout has been closed.
+fn exit_on_write_error(result: io::Result<()>) {
+    if let Err(error) = result {
+        eprintln!("Could not write to stdout: {}!", error);
+        process::exit(1);
+    }
+}
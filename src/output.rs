@@ -0,0 +1,173 @@
+/* Copyright (C) 2022  Fabian Moos
+ * This file is part of encodex.
+ *
+ * encodex is free software: you can redistribute it and/or modify it under the terms of the GNU
+ * General Public License as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * encodex is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without
+ * even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+ * General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along with encodex. If not,
+ * see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The number of bytes per line of a hex dump.
+pub const HEXDUMP_LINE_LEN: usize = 16;
+/// The file name of the split output files without their number.
+const SPLIT_FILE_PREFIX: &str = "encodex-split";
+
+/// Describes how the translated data is written to stdout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    /// The translated bytes as they are.
+    Raw,
+    /// An `xxd` style hex dump of the translated bytes.
+    Hexdump,
+    /// Nothing, the input is only checked for being well-formed.
+    Check,
+    /// The encoded bytes spread over numbered files that hold at most the given number of bytes
+    /// each, see [`SplitWriter`](SplitWriter).
+    Split(usize),
+}
+
+/// Writes encoded output across numbered files in a directory.
+///
+/// The files are named `encodex-split.000`, `encodex-split.001`, ... in the order they are
+/// written; the number has at least three digits and keeps counting across all outputs written
+/// with the same writer. The files hold no line breaks.
+///
+/// The size limit is a whole number of encoded groups, so every file ends on a group boundary:
+/// each file decodes on its own and the files decode in numbered order when concatenated.
+pub struct SplitWriter {
+    directory: PathBuf,
+    max_len: usize,
+    next_index: usize,
+}
+
+impl SplitWriter {
+    /// Creates a writer for files of at most `max_len` bytes in `directory`. `max_len` must be a
+    /// non-zero multiple of the length of one encoded group of the used base.
+    pub fn new(directory: &Path, max_len: usize) -> Self {
+        SplitWriter { directory: directory.to_path_buf(), max_len, next_index: 0 }
+    }
+
+    /// Writes `encoded` to as many new files as needed and returns their paths. Empty output
+    /// still creates one empty file.
+    pub fn write(&mut self, encoded: &[u8]) -> std::io::Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        let mut chunks: Vec<&[u8]> = encoded.chunks(self.max_len).collect();
+        if chunks.is_empty() { chunks.push(&[]); }
+        for chunk in chunks {
+            let path = self.directory.join(format!("{}.{:03}", SPLIT_FILE_PREFIX,
+                                                   self.next_index));
+            fs::write(&path, chunk)?;
+            self.next_index += 1;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+/// Formats `bytes` like `xxd` does: every line holds the offset of its first byte, up to 16
+/// bytes as hex digits in groups of two bytes and the printable ASCII characters of these bytes,
+/// all other bytes are shown as `.`. The offsets start at `start`, so consecutive blocks of a
+/// larger output can be dumped one after the other.
+pub fn hexdump(bytes: &[u8], start: usize) -> String {
+    let mut dump = String::new();
+    for (line, chunk) in bytes.chunks(HEXDUMP_LINE_LEN).enumerate() {
+        let mut hex = String::new();
+        for (index, byte) in chunk.iter().enumerate() {
+            if index > 0 && index % 2 == 0 { hex.push(' '); }
+            write!(hex, "{:02x}", byte).unwrap();
+        }
+        let text: String = chunk.iter()
+                                .map(|&byte| {
+                                    if byte.is_ascii_graphic() || byte == b' ' {
+                                        char::from(byte)
+                                    } else {
+                                        '.'
+                                    }
+                                })
+                                .collect();
+        let offset = start + line * HEXDUMP_LINE_LEN;
+        writeln!(dump, "{:08x}: {:<39}  {}", offset, hex, text).unwrap();
+    }
+    dump
+}
+
+/// Tests for formatting the output.
+#[cfg(any(test, feature = "doc_tests"))]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_hexdump() {
+        let dump = hexdump(b"hello\nworld, this is a longer line\x00\x01", 0);
+        assert_eq!(dump, concat!(
+            "00000000: 6865 6c6c 6f0a 776f 726c 642c 2074 6869  hello.world, thi\n",
+            "00000010: 7320 6973 2061 206c 6f6e 6765 7220 6c69  s is a longer li\n",
+            "00000020: 6e65 0001                                ne..\n"));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_hexdump_odd_length() {
+        assert_eq!(hexdump(b"hel", 0), "00000000: 6865 6c                                  hel\n");
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_hexdump_start() {
+        assert_eq!(hexdump(b"hel", 0x30),
+                   "00000030: 6865 6c                                  hel\n");
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_hexdump_empty() {
+        assert_eq!(hexdump(b"", 0), "");
+    }
+
+    #[cfg(feature = "base16")]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_hexdump_readable_as_hexdump_input() {
+        let bytes: Vec<u8> = (0..=40).collect();
+        let mut config = encodex::Settings::new();
+        config.set_base(encodex::Base::Base16);
+        config.set_encode_mode(encodex::EncodeMode::Decode);
+        config.set_hexdump_input(true);
+        let mut unit = encodex::TranslationUnit::from_input(hexdump(&bytes, 0).as_str(), config);
+        assert!(unit.translate().is_ok());
+        assert_eq!(unit.into_decoded(), Some(bytes));
+    }
+
+    #[cfg(feature = "base64")]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_split_writer_two_files() {
+        let directory = std::env::temp_dir().join(format!("encodex-split-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        // 10 bytes are 16 Base64 characters, that is 2 groups per file.
+        let mut writer = SplitWriter::new(&directory, 8);
+        let paths = writer.write(b"Zm9vYmFyYmF6IQ==").unwrap();
+        let files: Vec<Vec<u8>> = paths.iter().map(|path| fs::read(path).unwrap()).collect();
+        let names: Vec<_> = paths.iter().map(|path| path.file_name().unwrap().to_owned()).collect();
+        fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(names, ["encodex-split.000", "encodex-split.001"]);
+        assert_eq!(files, [b"Zm9vYmFy".to_vec(), b"YmF6IQ==".to_vec()]);
+
+        let mut config = encodex::Settings::new();
+        config.set_base(encodex::Base::Base64);
+        config.set_encode_mode(encodex::EncodeMode::Decode);
+        for (file, expected) in files.iter().zip([&b"foobar"[..], b"baz!"]) {
+            let mut unit = encodex::TranslationUnit::new(file.clone(), config.clone());
+            assert!(unit.translate().is_ok());
+            assert_eq!(unit.into_decoded(), Some(expected.to_vec()));
+        }
+        let mut unit = encodex::TranslationUnit::new(files.concat(), config);
+        assert!(unit.translate().is_ok());
+        assert_eq!(unit.into_decoded(), Some(b"foobarbaz!".to_vec()));
+    }
+}
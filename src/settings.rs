@@ -15,9 +15,27 @@
 
 //! Handles the configuration of [`TranslationUnit`](crate::TranslationUnit)s.
 
+use std::{fmt, str};
+
+use crate::error::EncodeError;
+
 /// Describes all available Base encodings.
 ///
-#[derive(Clone, Copy)]
+/// # Compatibility with RFC 3548
+///
+/// `Base64`, `Base64url`, `Base32` and `Base16` follow **RFC 4648**, which obsoletes
+/// **RFC 3548**. Both specifications use the same alphabets and the same padding, so data encoded
+/// under the older RFC decodes unchanged and no separate preset is needed. `Base32hex` was only
+/// added by RFC 4648 (7). Where the older RFC left room for interpretation, the behavior is:
+///
+/// * Padding is written and, by default, required. Unpadded data needs
+///   [mixed padding](Settings::set_mixed_padding).
+/// * Only uppercase letters are written and accepted by `Base32`, `Base32hex` and `Base16`
+///   unless [lowercase](Settings::set_lowercase) is set.
+/// * Non-zero unused bits in the last character are ignored unless
+///   [strict](Settings::set_strict) decoding is enabled.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Base {
     /// Alphabet:
     ///
@@ -40,20 +58,499 @@ pub enum Base {
     /// The `Base64url` encoding has the same alphabet as the `Base64` alphabet. Only `+` and `/`
     /// are replaced by `-` (minus) and `_` (underscore) respectively.
     Base64url,
-    /// todo
+    /// Alphabet:
+    ///
+    /// | Value | Encoding | Value | Encoding | Value | Encoding | Value | Encoding |
+    /// |------:|:---------|------:|:---------|------:|:---------|------:|:---------|
+    /// |     0 | `A`      |     9 | `J`      |    18 | `S`      |    27 | `3`      |
+    /// |     1 | `B`      |    10 | `K`      |    19 | `T`      |    28 | `4`      |
+    /// |     2 | `C`      |    11 | `L`      |    20 | `U`      |    29 | `5`      |
+    /// |     3 | `D`      |    12 | `M`      |    21 | `V`      |    30 | `6`      |
+    /// |     4 | `E`      |    13 | `N`      |    22 | `W`      |    31 | `7`      |
+    /// |     5 | `F`      |    14 | `O`      |    23 | `X`      |       |          |
+    /// |     6 | `G`      |    15 | `P`      |    24 | `Y`      | (pad) | `=`      |
+    /// |     7 | `H`      |    16 | `Q`      |    25 | `Z`      |       |          |
+    /// |     8 | `I`      |    17 | `R`      |    26 | `2`      |       |          |
     Base32,
-    /// todo
+    /// The `Base32hex` encoding uses the digits `0` to `9` for the values 0 to 9 and the letters
+    /// `A` to `V` for the values 10 to 31. The padding character is `=`, like in `Base32`.
     Base32hex,
-    /// todo
+    /// The `Base16` encoding uses the digits `0` to `9` for the values 0 to 9 and the letters `A`
+    /// to `F` for the values 10 to 15. No padding is needed.
     Base16,
+    /// The modified `UTF-7` encoding of mailbox names as defined in **RFC 3501** (5.1.3).
+    ///
+    /// Printable US-ASCII characters except `&` represent themselves, `&` is encoded as `&-`. All
+    /// other characters are encoded as `UTF-16` in a variant of `Base64` that uses `,` instead of
+    /// `/` and no padding, enclosed in `&` and `-`. The decoded data has to be valid `UTF-8`.
+    ImapUtf7,
+    /// Douglas Crockford's `Base32` encoding, which reads the data as one big-endian number.
+    ///
+    /// The digits `0` to `9` encode the values 0 to 9, the letters `A` to `Z` without `I`, `L`,
+    /// `O` and `U` encode the values 10 to 31. The number is padded with zero bits at its front
+    /// instead of padding characters at its end. Decoding ignores the case of the letters and
+    /// hyphens, `O` is read as `0`, `I` and `L` are read as `1`. See
+    /// [`set_checksum_symbol`](Settings::set_checksum_symbol) for the optional check symbol.
+    Crockford32,
+    /// The `Base45` encoding as defined in **RFC 9285**, which is used for QR codes.
+    ///
+    /// Every two bytes are read as a big-endian number `n` and encoded as three characters `c`,
+    /// `d` and `e` with `n = c + d * 45 + e * 45²`, a single trailing byte is encoded as two
+    /// characters. The digits `0` to `9` encode the values 0 to 9, the letters `A` to `Z` the
+    /// values 10 to 35, followed by ` `, `$`, `%`, `*`, `+`, `-`, `.`, `/` and `:` for the values
+    /// 36 to 44. No padding is needed.
+    Base45,
+    /// The `Bech32` encoding as defined in **BIP-0173**, which is used for addresses.
+    ///
+    /// The encoding consists of a human-readable part, the separator `1`, the data in groups of 5
+    /// bits and a checksum of six characters. The characters `q`, `p`, `z`, `r`, `y`, `9`, `x`,
+    /// `8`, `g`, `f`, `2`, `t`, `v`, `d`, `w`, `0`, `s`, `3`, `j`, `n`, `5`, `4`, `k`, `h`, `c`,
+    /// `e`, `6`, `m`, `u`, `a`, `7` and `l` encode the values 0 to 31, the last group is padded
+    /// with zero bits. Encoding uses the human-readable part of
+    /// [`set_bech32_hrp`](Settings::set_bech32_hrp), decoding verifies the checksum. The encoded
+    /// data is at most 90 characters long.
+    Bech32,
+    /// A `Base32` variant for file names that are derived from binary data, like identifiers or
+    /// digests.
+    ///
+    /// The digits `0` to `9` encode the values 0 to 9 and the lowercase letters `a` to `v` the
+    /// values 10 to 31, like a lowercase `Base32hex`. There is no padding, the last group is
+    /// shortened instead. So the encoded data only contains characters that are valid in file
+    /// names on Windows, macOS and Linux without escaping, and names that differ only in case
+    /// can't collide on case-insensitive file systems. The encoding preserves the sort order of
+    /// the data. Decoding ignores the case of the letters.
+    FilenameSafe,
+    /// The `Base32` alphabet of geohashes, `0123456789bcdefghjkmnpqrstuvwxyz`, which leaves out
+    /// the letters `a`, `i`, `l` and `o`.
+    ///
+    /// The data is split into groups of 5 bits like `Base32`, so the bits of a geohash can be
+    /// encoded and decoded byte-wise. There is no padding, the last group is shortened instead.
+    /// Decoding ignores the case of the letters.
+    Base32Geohash,
+    /// A preset for `Base64url` tokens of web applications, like session tokens or the parts of
+    /// a JSON Web Token.
+    ///
+    /// Encoding writes `Base64url` without padding. Decoding accepts the characters of both the
+    /// `Base64url` and the `Base64` alphabet, even mixed, with or without padding, and ignores
+    /// whitespace.
+    WebToken,
     /// todo
     Guess,
 }
 
+impl Base {
+    /// Returns the number of decoded bytes that make up one complete group of this
+    /// [`Base`](Base).
+    ///
+    /// A byte slice whose length is a multiple of this value can be encoded without padding, so
+    /// encoding it separately yields the same characters as encoding it as part of a larger input.
+    /// [`Guess`](Base::Guess) returns the least common multiple of all group sizes, which is
+    /// aligned for every other base. [`ImapUtf7`](Base::ImapUtf7) has no fixed group size and
+    /// [`Crockford32`](Base::Crockford32) and [`Bech32`](Base::Bech32) encode their data as one
+    /// number or with one checksum, so they return [`usize::MAX`] and their data is never split.
+    pub fn chunk_size(&self) -> usize {
+        match self {
+            Base::Base64 | Base::Base64url | Base::WebToken => { 3 }
+            Base::Base32 | Base::Base32hex | Base::FilenameSafe | Base::Base32Geohash => { 5 }
+            Base::Base16 => { 1 }
+            Base::Base45 => { 2 }
+            Base::ImapUtf7 | Base::Crockford32 | Base::Bech32 => { usize::MAX }
+            // The least common multiple of the group sizes 3, 5, 2 and 1.
+            Base::Guess => { 30 }
+        }
+    }
+
+    /// Returns the number of characters that make up one complete encoded group of this
+    /// [`Base`](Base).
+    pub(crate) fn encoded_chunk_size(&self) -> usize {
+        match self {
+            Base::Base64 | Base::Base64url | Base::WebToken => { 4 }
+            Base::Base32 | Base::Base32hex | Base::FilenameSafe | Base::Base32Geohash => { 8 }
+            Base::Base16 => { 2 }
+            Base::Base45 => { 3 }
+            Base::ImapUtf7 | Base::Crockford32 | Base::Bech32 => { 1 }
+            Base::Guess => { 8 }
+        }
+    }
+
+    /// Returns an upper bound of the number of characters that encoding `len` bytes as this
+    /// [`Base`](Base) produces with the default settings.
+    ///
+    /// The length is computed without overflowing [`usize`], which large inputs can do on 32-bit
+    /// targets. Fails with [`EncodeError::InputTooLarge`] if the length doesn't fit into a
+    /// [`usize`] and with [`EncodeError::UnsupportedBase`] for [`Guess`](Base::Guess). The
+    /// length of [`Bech32`](Base::Bech32) includes the checksum, but not the human-readable part
+    /// and the separator.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// assert_eq!(encodex::Base::Base64.encoded_len(4), Ok(8));
+    /// assert!(encodex::Base::Base64.encoded_len(usize::MAX).is_err());
+    /// ```
+    pub fn encoded_len(&self, len: usize) -> Result<usize, EncodeError> {
+        let too_large = |limit| EncodeError::InputTooLarge { length: len, limit };
+        match self {
+            Base::Base64 | Base::Base64url | Base::Base32 | Base::Base32hex | Base::Base16
+            | Base::Base45 => {
+                let (bytes, characters) = (self.chunk_size(), self.encoded_chunk_size());
+                len.div_ceil(bytes)
+                   .checked_mul(characters)
+                   .ok_or_else(|| too_large(usize::MAX / characters * bytes))
+            }
+            // The last group is shortened instead of padded.
+            Base::FilenameSafe | Base::Base32Geohash | Base::WebToken => {
+                let bits_per_character = self.bits_per_character().unwrap();
+                len.checked_mul(8)
+                   .map(|bits| bits.div_ceil(bits_per_character))
+                   .ok_or_else(|| too_large(usize::MAX / 8))
+            }
+            Base::Crockford32 | Base::Bech32 => {
+                // The check symbol or the checksum follow the data characters.
+                let check_len = if *self == Base::Crockford32 { 1 } else { 6 };
+                len.checked_mul(8)
+                   .and_then(|bits| bits.div_ceil(5).checked_add(check_len))
+                   .ok_or_else(|| too_large(usize::MAX / 8))
+            }
+            // `&` and every isolated non-ASCII character need more than one character per byte.
+            Base::ImapUtf7 => { len.checked_mul(3).ok_or_else(|| too_large(usize::MAX / 3)) }
+            Base::Guess => { Err(EncodeError::UnsupportedBase(Base::Guess)) }
+        }
+    }
+
+    /// Returns an upper bound of the number of bytes that decoding `len` characters encoded as
+    /// this [`Base`](Base) produces with the default settings.
+    ///
+    /// Padding characters are counted like data characters, so the bound is exact for unpadded
+    /// data only. Fails with [`EncodeError::UnsupportedBase`] for [`Guess`](Base::Guess).
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// assert_eq!(encodex::Base::Base64.decoded_len(8), Ok(6));
+    /// assert_eq!(encodex::Base::Base32.decoded_len(7), Ok(4));
+    /// ```
+    pub fn decoded_len(&self, len: usize) -> Result<usize, EncodeError> {
+        match self {
+            Base::Base64 | Base::Base64url | Base::Base32 | Base::Base32hex | Base::Base16
+            | Base::Crockford32 | Base::Bech32 | Base::FilenameSafe | Base::Base32Geohash
+            | Base::WebToken => {
+                // Computed per group, so `len * bits` can't overflow.
+                let bits = self.bits_per_character().unwrap();
+                let characters = 8 / gcd(bits, 8);
+                Ok(len / characters * (characters * bits / 8) + len % characters * bits / 8)
+            }
+            Base::Base45 => { Ok(len / 3 * 2 + len % 3 * 2 / 3) }
+            // Every UTF-16 unit needs at least 8/3 characters and becomes at most 3 bytes of UTF-8.
+            Base::ImapUtf7 => { Ok(len / 8 * 9 + (len % 8 * 9).div_ceil(8)) }
+            Base::Guess => { Err(EncodeError::UnsupportedBase(Base::Guess)) }
+        }
+    }
+
+    /// Returns the asymptotic ratio of encoded characters to decoded bytes of this
+    /// [`Base`](Base), e.g. `4/3` for [`Base64`](Base::Base64).
+    ///
+    /// The ratio ignores padding, checksums and other constant overhead, so it compares the
+    /// overhead of the bases for large inputs without encoding anything.
+    /// [`ImapUtf7`](Base::ImapUtf7) returns `1.0`, the ratio of printable US-ASCII text, which
+    /// represents itself. [`Guess`](Base::Guess) has no ratio and returns [`f64::NAN`].
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// assert_eq!(encodex::Base::Base16.expansion_ratio(), 2.0);
+    /// ```
+    pub fn expansion_ratio(&self) -> f64 {
+        match self {
+            Base::Base64 | Base::Base64url | Base::WebToken => { 4.0 / 3.0 }
+            Base::Base32 | Base::Base32hex | Base::Crockford32 | Base::Bech32
+            | Base::FilenameSafe | Base::Base32Geohash => {
+                8.0 / 5.0
+            }
+            Base::Base16 => { 2.0 }
+            Base::Base45 => { 3.0 / 2.0 }
+            Base::ImapUtf7 => { 1.0 }
+            Base::Guess => { f64::NAN }
+        }
+    }
+
+    /// Returns the number of bits that are encoded by a single character of this
+    /// [`Base`](Base), if all characters encode the same number of bits.
+    pub(crate) fn bits_per_character(&self) -> Option<usize> {
+        match self {
+            Base::Base64 | Base::Base64url | Base::WebToken => { Some(6) }
+            Base::Base32 | Base::Base32hex | Base::Crockford32 | Base::Bech32
+            | Base::FilenameSafe | Base::Base32Geohash => {
+                Some(5)
+            }
+            Base::Base16 => { Some(4) }
+            _ => { None }
+        }
+    }
+
+    /// Returns the characters that encode the values of this [`Base`](Base), ordered by their
+    /// value.
+    ///
+    /// [`ImapUtf7`](Base::ImapUtf7) returns the alphabet of its shift sequences. The check
+    /// symbols of [`Crockford32`](Base::Crockford32) are not part of its alphabet.
+    /// [`Guess`](Base::Guess) has no alphabet.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// assert_eq!(encodex::Base::Base16.alphabet(), Some("0123456789ABCDEF"));
+    /// ```
+    pub fn alphabet(&self) -> Option<&'static str> {
+        match self {
+            Base::Base64 => {
+                Some("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/")
+            }
+            Base::Base64url | Base::WebToken => {
+                Some("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_")
+            }
+            Base::Base32 => { Some("ABCDEFGHIJKLMNOPQRSTUVWXYZ234567") }
+            Base::Base32hex => { Some("0123456789ABCDEFGHIJKLMNOPQRSTUV") }
+            Base::Base16 => { Some("0123456789ABCDEF") }
+            Base::ImapUtf7 => {
+                Some("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+,")
+            }
+            Base::Crockford32 => { Some("0123456789ABCDEFGHJKMNPQRSTVWXYZ") }
+            Base::Base45 => { Some("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:") }
+            Base::Bech32 => { Some("qpzry9x8gf2tvdw0s3jn54khce6mua7l") }
+            Base::FilenameSafe => { Some("0123456789abcdefghijklmnopqrstuv") }
+            Base::Base32Geohash => { Some("0123456789bcdefghjkmnpqrstuvwxyz") }
+            Base::Guess => { None }
+        }
+    }
+
+    /// Returns the index of every byte of `input` that can't be part of data encoded as this
+    /// [`Base`](Base), without decoding it.
+    ///
+    /// Valid are the characters of the [alphabet](Base::alphabet) and the padding. For
+    /// [`Crockford32`](Base::Crockford32) the lowercase letters, the aliases `O`, `I` and `L`,
+    /// hyphens and a check symbol as the last character are valid as well. The human-readable
+    /// part of [`Bech32`](Base::Bech32) data may contain every character from 33 to 126 of
+    /// US-ASCII, [`ImapUtf7`](Base::ImapUtf7) every printable US-ASCII character outside of its
+    /// shift sequences. [`Guess`](Base::Guess) accepts the characters that
+    /// [`ImapUtf7`](Base::ImapUtf7) accepts. Characters that are valid on their own, but not at
+    /// their position, like misplaced padding, are not reported.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// assert_eq!(encodex::Base::Base64.invalid_positions(b"Zm9v!YmF?"), vec![4, 8]);
+    /// ```
+    pub fn invalid_positions(&self, input: &[u8]) -> Vec<usize> {
+        let is_printable = |byte: u8| (0x20..=0x7E).contains(&byte);
+        let in_alphabet = |byte: u8| self.alphabet().is_some_and(|alphabet| {
+            alphabet.as_bytes().contains(&byte)
+        });
+        let mut positions = Vec::new();
+        match self {
+            Base::Crockford32 => {
+                let last = input.iter().rposition(|&byte| byte != b'-');
+                for (position, &byte) in input.iter().enumerate() {
+                    let byte_upper = byte.to_ascii_uppercase();
+                    let valid = in_alphabet(byte_upper) || b"OIL-".contains(&byte_upper)
+                        || (Some(position) == last && b"*~$=U".contains(&byte_upper));
+                    if !valid { positions.push(position); }
+                }
+            }
+            Base::FilenameSafe | Base::Base32Geohash => {
+                positions.extend((0..input.len()).filter(|&position| {
+                    !in_alphabet(input[position].to_ascii_lowercase())
+                }));
+            }
+            Base::WebToken => {
+                positions.extend((0..input.len()).filter(|&position| {
+                    let byte = input[position];
+                    !in_alphabet(byte) && !b"+/=".contains(&byte) && !byte.is_ascii_whitespace()
+                }));
+            }
+            Base::Bech32 => {
+                let separator = input.iter().rposition(|&byte| byte == b'1');
+                for (position, &byte) in input.iter().enumerate() {
+                    let valid = match separator {
+                        Some(separator) if position > separator => {
+                            in_alphabet(byte.to_ascii_lowercase())
+                        }
+                        _ => { (33..=126).contains(&byte) }
+                    };
+                    if !valid { positions.push(position); }
+                }
+            }
+            Base::ImapUtf7 => {
+                let mut shifted = false;
+                for (position, &byte) in input.iter().enumerate() {
+                    let valid = match (shifted, byte) {
+                        (false, b'&') | (true, b'-') => { shifted = !shifted; true }
+                        (false, byte) => { is_printable(byte) }
+                        (true, byte) => { in_alphabet(byte) }
+                    };
+                    if !valid { positions.push(position); }
+                }
+            }
+            Base::Guess => {
+                positions.extend((0..input.len()).filter(|&position| {
+                    !is_printable(input[position])
+                }));
+            }
+            _ => {
+                let padding = self.padding().map(|padding| padding as u8);
+                positions.extend((0..input.len()).filter(|&position| {
+                    !in_alphabet(input[position]) && Some(input[position]) != padding
+                }));
+            }
+        }
+        positions
+    }
+
+    /// Returns whether data encoded as this [`Base`](Base) can be read and typed by humans without
+    /// confusing look-alike characters, like `0`, `O` and `o` or `1`, `I` and `l`.
+    ///
+    /// An alphabet is confusable if it contains two or more characters of the same group, like
+    /// [`Base32hex`](Base::Base32hex) contains `0` and `O`, or if it contains one character of a
+    /// group and decoding rejects the others, like [`Base32`](Base::Base32) contains `O` but
+    /// rejects a mistyped `0`. [`Crockford32`](Base::Crockford32) decodes every look-alike as the
+    /// character of its alphabet. [`Guess`](Base::Guess) has no alphabet and returns `false`.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// assert!(encodex::Base::Crockford32.is_confusable_free());
+    /// assert!(!encodex::Base::Base32hex.is_confusable_free());
+    /// ```
+    pub fn is_confusable_free(&self) -> bool {
+        const LOOK_ALIKES: [&str; 2] = ["0Oo", "1Il"];
+        let alphabet = match self.alphabet() {
+            Some(alphabet) => { alphabet }
+            None => { return false; }
+        };
+        LOOK_ALIKES.iter().all(|group| {
+            let contained = group.chars().filter(|&character| alphabet.contains(character)).count();
+            let accepted = group.bytes().filter(|&byte| self.invalid_positions(&[byte]).is_empty())
+                                        .count();
+            contained == 0 || (contained == 1 && accepted == group.len())
+        })
+    }
+
+    /// Returns a permutation of the [alphabet](Base::alphabet) of this [`Base`](Base) that is
+    /// derived deterministically from `seed`.
+    ///
+    /// The same seed always yields the same alphabet, on every platform and in every version.
+    /// This is meant for lightweight obfuscation, **not for encryption**. Returns [`None`] for
+    /// [`ImapUtf7`](Base::ImapUtf7) and [`Bech32`](Base::Bech32), whose encoded data contains
+    /// characters outside of their alphabet, and for [`Guess`](Base::Guess).
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// use encodex::Base;
+    ///
+    /// let alphabet = Base::Base16.seeded_alphabet(7).unwrap();
+    /// assert_eq!(alphabet, Base::Base16.seeded_alphabet(7).unwrap());
+    /// assert_ne!(alphabet, Base::Base16.seeded_alphabet(8).unwrap());
+    /// ```
+    pub fn seeded_alphabet(&self, seed: u64) -> Option<String> {
+        let mut alphabet = match self {
+            Base::ImapUtf7 | Base::Bech32 | Base::Guess => { return None; }
+            base => { base.alphabet().unwrap().as_bytes().to_vec() }
+        };
+        // A Fisher-Yates shuffle driven by SplitMix64.
+        let mut state = seed;
+        for index in (1..alphabet.len()).rev() {
+            state = state.wrapping_add(0x9E3779B97F4A7C15);
+            let mut random = state;
+            random = (random ^ (random >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            random = (random ^ (random >> 27)).wrapping_mul(0x94D049BB133111EB);
+            random ^= random >> 31;
+            alphabet.swap(index, (random % (index as u64 + 1)) as usize);
+        }
+        // Every alphabet consists of ASCII characters only.
+        Some(String::from_utf8(alphabet).unwrap())
+    }
+
+    /// Returns the character that pads incomplete groups of this [`Base`](Base), if it uses
+    /// padding.
+    pub fn padding(&self) -> Option<char> {
+        match self {
+            Base::Base64 | Base::Base64url | Base::Base32 | Base::Base32hex => { Some('=') }
+            _ => { None }
+        }
+    }
+
+    /// Splits `data` into slices that can be en-/decoded independently of each other.
+    ///
+    /// Every yielded slice has a length that is a multiple of [`chunk_size`](Base::chunk_size),
+    /// except for the last one. `approx` is the preferred length of a slice, it is rounded down to
+    /// the next multiple of [`chunk_size`](Base::chunk_size), but never below one group.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// let data = [0u8; 10];
+    /// let lengths: Vec<usize> = encodex::Base::Base64.align_chunks(&data, 4)
+    ///                                                .map(|chunk| chunk.len())
+    ///                                                .collect();
+    ///
+    /// assert_eq!(lengths, vec![3, 3, 3, 1]);
+    /// ```
+    pub fn align_chunks<'a>(&self, data: &'a [u8], approx: usize)
+                            -> impl Iterator<Item = &'a [u8]> {
+        let chunk_size = self.chunk_size();
+        let slice_len = std::cmp::max(approx / chunk_size, 1) * chunk_size;
+        data.chunks(slice_len)
+    }
+}
+
+impl fmt::Display for Base {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Base::Base64 => { write!(f, "Base64") }
+            Base::Base64url => { write!(f, "Base64url") }
+            Base::Base32 => { write!(f, "Base32") }
+            Base::Base32hex => { write!(f, "Base32hex") }
+            Base::Base16 => { write!(f, "Base16") }
+            Base::ImapUtf7 => { write!(f, "ImapUtf7") }
+            Base::Crockford32 => { write!(f, "Crockford32") }
+            Base::Base45 => { write!(f, "Base45") }
+            Base::Bech32 => { write!(f, "Bech32") }
+            Base::FilenameSafe => { write!(f, "FilenameSafe") }
+            Base::Base32Geohash => { write!(f, "Base32Geohash") }
+            Base::WebToken => { write!(f, "WebToken") }
+            Base::Guess => { write!(f, "Guess") }
+        }
+    }
+}
+
+impl str::FromStr for Base {
+    type Err = EncodeError;
+
+    /// Parses the name of a [`Base`](Base), ignoring the case of the letters.
+    fn from_str(name: &str) -> Result<Base, EncodeError> {
+        match &name.to_ascii_lowercase()[..] {
+            "base64" => { Ok(Base::Base64) }
+            "base64url" => { Ok(Base::Base64url) }
+            "base32" => { Ok(Base::Base32) }
+            "base32hex" => { Ok(Base::Base32hex) }
+            "base16" => { Ok(Base::Base16) }
+            "imaputf7" => { Ok(Base::ImapUtf7) }
+            "crockford32" => { Ok(Base::Crockford32) }
+            "base45" => { Ok(Base::Base45) }
+            "bech32" => { Ok(Base::Bech32) }
+            "filenamesafe" => { Ok(Base::FilenameSafe) }
+            "base32geohash" => { Ok(Base::Base32Geohash) }
+            "webtoken" => { Ok(Base::WebToken) }
+            "guess" => { Ok(Base::Guess) }
+            _ => { Err(EncodeError::UnknownBase(String::from(name))) }
+        }
+    }
+}
+
 /// The encode mode that is used.
 ///
 /// Default is [`Encode`](EncodeMode::Encode).
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum EncodeMode {
     /// Decode the given input. Translate a Base encoded String into a byte vector.
     Decode,
@@ -62,11 +559,117 @@ pub enum EncodeMode {
     Encode,
 }
 
+impl str::FromStr for EncodeMode {
+    type Err = EncodeError;
+
+    /// Parses `encode` or `decode`, ignoring the case of the letters.
+    fn from_str(name: &str) -> Result<EncodeMode, EncodeError> {
+        match &name.to_ascii_lowercase()[..] {
+            "decode" => { Ok(EncodeMode::Decode) }
+            "encode" => { Ok(EncodeMode::Encode) }
+            _ => { Err(EncodeError::UnknownEncodeMode(String::from(name))) }
+        }
+    }
+}
+
+/// Returns the greatest common divisor of `a` and `b`.
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// A hint about the original bytes of [`Base64`](crate::Base::Base64) encoded data whose letters
+/// have all been converted to the same case, see
+/// [`set_base64_case_fold`](Settings::set_base64_case_fold).
+///
+/// Default is [`Disabled`](CaseFold::Disabled).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CaseFold {
+    /// The case of the encoded data is not changed.
+    Disabled,
+    /// The original bytes are printable ASCII text that is mostly written in lowercase.
+    LowercaseText,
+    /// The original bytes are printable ASCII text that is mostly written in uppercase.
+    UppercaseText,
+}
+
+/// The order of the bytes in [`Base16`](crate::Base::Base16) encoded data, see
+/// [`set_hex_byte_order`](Settings::set_hex_byte_order).
+///
+/// Default is [`BigEndian`](ByteOrder::BigEndian).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ByteOrder {
+    /// The first byte is encoded first, so `[0x12, 0x34]` is encoded as `1234`.
+    BigEndian,
+    /// The last byte is encoded first, so `[0x12, 0x34]` is encoded as `3412`, which reads as the
+    /// number `0x1234` of a little-endian memory dump.
+    LittleEndian,
+}
+
+/// The order in which the bits of a group are packed into the encoded characters, see
+/// [`set_bit_order`](Settings::set_bit_order).
+///
+/// The layouts are described for the `Base64` group of three bytes `b0`, `b1` and `b2` and the
+/// four characters `c0` to `c3`. `Base32` groups of five bytes and eight characters, and
+/// `Base16` groups of one byte and two characters, are packed the same way. Missing bytes of an
+/// incomplete last group are zero and only the characters that hold bits of the data are
+/// written.
+///
+/// Default is [`MsbFirst`](BitOrder::MsbFirst).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BitOrder {
+    /// The order of **RFC 4648**: the group is the number `b0 << 16 | b1 << 8 | b2`, and `c0`
+    /// holds its bits 23 to 18, `c1` the bits 17 to 12 and so on. `[0x66]` is encoded as `Zg==`.
+    MsbFirst,
+    /// The order of the `crypt` password hashes: the group is the number
+    /// `b2 << 16 | b1 << 8 | b0`, and `c0` holds its bits 5 to 0, `c1` the bits 11 to 6 and so
+    /// on. `[0x66]` is encoded as `mB==`. In `Base16` the low nibble is written first.
+    LsbFirst,
+}
+
 /// Describes how a [`TranslationUnit`](crate::TranslationUnit) handles its input.
-#[derive(Clone, Copy)]
+///
+/// [`Settings`] are [`Clone`], but not [`Copy`]. Options of variable length, like a custom
+/// alphabet, are stored as owned values, so every
+/// [`TranslationUnit`](crate::TranslationUnit) takes its own settings by value. Clone the
+/// settings to create several units from the same configuration.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Settings {
     base: Base,
     encode_mode: EncodeMode,
+    output_block_align: Option<usize>,
+    max_input_len: Option<usize>,
+    lowercase: bool,
+    separator: Option<u8>,
+    expected_output_len: Option<usize>,
+    decode_passes: usize,
+    checksum_symbol: bool,
+    ignore_chars: Vec<u8>,
+    strict: bool,
+    multi_message: bool,
+    percent_encode_padding: bool,
+    pad_aliases: Vec<u8>,
+    digest_line: bool,
+    hexdump_input: bool,
+    base64_case_fold: CaseFold,
+    bech32_hrp: String,
+    error_context: bool,
+    group_size: Option<usize>,
+    group_separator: u8,
+    json_unescape: bool,
+    seeded_alphabet: Option<(Base, u64)>,
+    base32_fuzzy: bool,
+    line_continuation: bool,
+    normalize_homoglyphs: bool,
+    output_delimiter: Option<u8>,
+    keep_both: bool,
+    hex_byte_order: ByteOrder,
+    hex_nibble_swap: bool,
+    mixed_padding: bool,
+    auto_armor: bool,
+    readable: bool,
+    line_prefix: String,
+    checksum_suffix: bool,
+    bit_order: BitOrder,
 }
 
 impl Settings {
@@ -77,19 +680,901 @@ impl Settings {
         Settings {
             base: Base::Guess,
             encode_mode: EncodeMode::Encode,
+            output_block_align: None,
+            max_input_len: None,
+            lowercase: false,
+            separator: None,
+            expected_output_len: None,
+            decode_passes: 1,
+            checksum_symbol: false,
+            ignore_chars: Vec::new(),
+            strict: false,
+            multi_message: false,
+            percent_encode_padding: false,
+            pad_aliases: Vec::new(),
+            digest_line: false,
+            hexdump_input: false,
+            base64_case_fold: CaseFold::Disabled,
+            bech32_hrp: String::new(),
+            error_context: false,
+            group_size: None,
+            group_separator: b' ',
+            json_unescape: false,
+            seeded_alphabet: None,
+            base32_fuzzy: false,
+            line_continuation: false,
+            normalize_homoglyphs: false,
+            output_delimiter: None,
+            keep_both: false,
+            hex_byte_order: ByteOrder::BigEndian,
+            hex_nibble_swap: false,
+            mixed_padding: false,
+            auto_armor: false,
+            readable: false,
+            line_prefix: String::new(),
+            checksum_suffix: false,
+            bit_order: BitOrder::MsbFirst,
         }
     }
 
+    /// Creates a configuration from a compact specification of the form `<base>/<mode>`.
+    ///
+    /// `<base>` is the name of a [`Base`](Base) and `<mode>` is either `encode` or `decode`, both
+    /// ignoring the case of the letters. All other options have their default values.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// let settings = encodex::Settings::from_spec("base32hex/decode").unwrap();
+    ///
+    /// assert_eq!(settings.base(), encodex::Base::Base32hex);
+    /// assert_eq!(settings.encode_mode(), encodex::EncodeMode::Decode);
+    /// ```
+    pub fn from_spec(spec: &str) -> Result<Settings, EncodeError> {
+        let (base, mode) = match spec.split_once('/') {
+            Some(parts) => { parts }
+            None => { return Err(EncodeError::InvalidSpec(String::from(spec))); }
+        };
+        let mut settings = Settings::new();
+        settings.set_base(base.parse()?);
+        settings.set_encode_mode(mode.parse()?);
+        Ok(settings)
+    }
+
     /// Returns the [`Base`](Base) of this configuration.
     pub fn base(&self) -> Base { self.base }
 
     /// Returns the [encode mode](EncodeMode) of this configuration.
     pub fn encode_mode(&self) -> EncodeMode { self.encode_mode }
 
+    /// Returns the maximum number of input bytes a translation accepts, if any.
+    pub fn max_input_len(&self) -> Option<usize> { self.max_input_len }
+
+    /// Returns the number of characters the encoded output is aligned to, if any.
+    pub fn output_block_align(&self) -> Option<usize> { self.output_block_align }
+
+    /// Returns whether case-insensitive bases are encoded with lowercase letters.
+    pub fn lowercase(&self) -> bool { self.lowercase }
+
+    /// Returns the character that separates the encoded bytes of [`Base16`](Base::Base16), if any.
+    pub fn separator(&self) -> Option<u8> { self.separator }
+
+    /// Returns the number of bytes the output of a translation is expected to have, if any.
+    pub fn expected_output_len(&self) -> Option<usize> { self.expected_output_len }
+
+    /// Returns how many times the input is decoded.
+    pub fn decode_passes(&self) -> usize { self.decode_passes }
+
+    /// Returns whether a check symbol is appended to [`Crockford32`](Base::Crockford32) encoded
+    /// data.
+    pub fn checksum_symbol(&self) -> bool { self.checksum_symbol }
+
+    /// Returns the characters that are skipped when decoding.
+    pub fn ignore_chars(&self) -> &[u8] { &self.ignore_chars }
+
+    /// Returns whether only canonical encodings are accepted when decoding.
+    pub fn strict(&self) -> bool { self.strict }
+
+    /// Returns whether padded groups end a message when decoding.
+    pub fn multi_message(&self) -> bool { self.multi_message }
+
+    /// Returns whether the padding of [`Base64url`](Base::Base64url) is percent-encoded.
+    pub fn percent_encode_padding(&self) -> bool { self.percent_encode_padding }
+
+    /// Returns the characters that are decoded as padding in addition to `=`.
+    pub fn pad_aliases(&self) -> &[u8] { &self.pad_aliases }
+
+    /// Returns whether a digest line is appended when encoding and verified when decoding.
+    pub fn digest_line(&self) -> bool { self.digest_line }
+
+    /// Returns whether [`Base16`](Base::Base16) encoded data is read from a hex dump.
+    pub fn hexdump_input(&self) -> bool { self.hexdump_input }
+
+    /// Returns the hint that is used to recover the case of [`Base64`](Base::Base64) encoded data.
+    pub fn base64_case_fold(&self) -> CaseFold { self.base64_case_fold }
+
+    /// Returns the human-readable part of [`Bech32`](Base::Bech32) encoded data.
+    pub fn bech32_hrp(&self) -> &str { &self.bech32_hrp }
+
+    /// Returns whether decoding errors contain the encoded data around the error.
+    pub fn error_context(&self) -> bool { self.error_context }
+
+    /// Returns the number of encoded characters per group, if the output is grouped.
+    pub fn group_size(&self) -> Option<usize> { self.group_size }
+
+    /// Returns the character that separates the groups of the encoded output.
+    pub fn group_separator(&self) -> u8 { self.group_separator }
+
+    /// Returns whether JSON string escapes are removed before decoding.
+    pub fn json_unescape(&self) -> bool { self.json_unescape }
+
+    /// Returns the base and the seed of the [seeded alphabet](Settings::set_seeded_alphabet), if
+    /// one is set.
+    pub fn seeded_alphabet(&self) -> Option<(Base, u64)> { self.seeded_alphabet }
+
+    /// Returns whether commonly confused digits are accepted in [`Base32`](Base::Base32) data.
+    pub fn base32_fuzzy(&self) -> bool { self.base32_fuzzy }
+
+    /// Returns whether lines that end with a backslash are joined before decoding.
+    pub fn line_continuation(&self) -> bool { self.line_continuation }
+
+    /// Returns whether Unicode look-alikes of ASCII characters are replaced before decoding.
+    pub fn normalize_homoglyphs(&self) -> bool { self.normalize_homoglyphs }
+
+    /// Returns the byte the decoded data is split into records on, if any.
+    pub fn output_delimiter(&self) -> Option<u8> { self.output_delimiter }
+
+    /// Returns whether the original encoded data is kept unchanged when decoding.
+    pub fn keep_both(&self) -> bool { self.keep_both }
+
+    /// Returns the order of the bytes in [`Base16`](Base::Base16) encoded data.
+    pub fn hex_byte_order(&self) -> ByteOrder { self.hex_byte_order }
+
+    /// Returns whether the two hex digits of every byte are swapped in [`Base16`](Base::Base16)
+    /// encoded data.
+    pub fn hex_nibble_swap(&self) -> bool { self.hex_nibble_swap }
+
+    /// Returns whether padded and unpadded messages may be mixed in the encoded data.
+    pub fn mixed_padding(&self) -> bool { self.mixed_padding }
+
+    /// Returns whether armor lines around [`Base64`](Base::Base64) data are detected and removed.
+    pub fn auto_armor(&self) -> bool { self.auto_armor }
+
+    /// Returns whether the encoded data is made readable for humans.
+    pub fn readable(&self) -> bool { self.readable }
+
+    /// Returns the string that every line of the encoded data starts with.
+    pub fn line_prefix(&self) -> &str { &self.line_prefix }
+
+    /// Returns whether a checksum suffix is appended when encoding and verified when decoding.
+    pub fn checksum_suffix(&self) -> bool { self.checksum_suffix }
+
+    /// Returns the order in which the bits of a group are packed into the encoded characters.
+    pub fn bit_order(&self) -> BitOrder { self.bit_order }
+
     /// Set a new [`Base`](Base) value for this configuration.
     pub fn set_base(&mut self, base: Base) { self.base = base; }
 
     /// Set a new [encode mode](EncodeMode) for this configuration.
     pub fn set_encode_mode(&mut self, mode: EncodeMode) { self.encode_mode = mode; }
+
+    /// Aligns the encoded output to a multiple of `align` characters.
+    ///
+    /// When encoding, the output is filled up with `#`, which is part of no alphabet. The filler
+    /// is appended after the regular `=` padding, so `f` aligned to 8 characters is encoded as
+    /// `Zg==####` and `foo` as `Zm9v####`. `None` and `Some(0)` disable the alignment, which is
+    /// the default.
+    ///
+    /// When decoding, every `#` at the end of the encoded data is stripped before it is decoded.
+    /// [`ImapUtf7`](Base::ImapUtf7) encodes `#` as itself and fails with
+    /// [`UnsupportedBase`](crate::EncodeError::UnsupportedBase) if the alignment is enabled.
+    pub fn set_output_block_align(&mut self, align: Option<usize>) {
+        self.output_block_align = align;
+    }
+
+    /// Limits the number of input bytes a translation accepts.
+    ///
+    /// If the data of a [`TranslationUnit`](crate::TranslationUnit) is longer than `limit`,
+    /// [`translate`](crate::TranslationUnit::translate) fails with
+    /// [`EncodeError::InputTooLarge`](crate::EncodeError::InputTooLarge) before any output is
+    /// allocated. Use this when decoding untrusted input. Default is `None` (unlimited).
+    pub fn set_max_input_len(&mut self, limit: Option<usize>) { self.max_input_len = limit; }
+
+    /// Encodes [`Base32`](Base::Base32), [`Base32hex`](Base::Base32hex) and
+    /// [`Base16`](Base::Base16) with lowercase instead of uppercase letters.
+    ///
+    /// When decoding, lowercase letters are accepted for these bases. All other bases are not
+    /// affected. Default is `false`.
+    pub fn set_lowercase(&mut self, lowercase: bool) { self.lowercase = lowercase; }
+
+    /// Separates the two characters of every encoded byte of [`Base16`](Base::Base16) from the
+    /// next with `separator`, e.g. `66:6F:6F` for `foo` and `:`.
+    ///
+    /// When decoding, every occurrence of `separator` is removed before the data is decoded. All
+    /// other bases are not affected. Default is `None`.
+    pub fn set_separator(&mut self, separator: Option<u8>) { self.separator = separator; }
+
+    /// Verifies the length of the output of a translation.
+    ///
+    /// After encoding, the number of encoded characters including all separators, padding and
+    /// filler is compared to `length`. After decoding, the number of decoded bytes is compared.
+    /// If they differ, [`translate`](crate::TranslationUnit::translate) fails with
+    /// [`EncodeError::LengthMismatch`](crate::EncodeError::LengthMismatch). Default is `None`.
+    pub fn set_expected_output_len(&mut self, length: Option<usize>) {
+        self.expected_output_len = length;
+    }
+
+    /// Decodes data that has been encoded `passes` times.
+    ///
+    /// The output of every pass is decoded again by the next pass with the same settings, so
+    /// `Wm05dg==` (the `Base64` encoding of `Zm9v`) decodes to `foo` with two passes. Every pass
+    /// validates its input on its own. `0` behaves like `1`, which is the default.
+    pub fn set_decode_passes(&mut self, passes: usize) { self.decode_passes = passes; }
+
+    /// Appends a check symbol to [`Crockford32`](Base::Crockford32) encoded data and verifies it
+    /// when decoding.
+    ///
+    /// The check symbol encodes the value of the decoded bytes modulo 37, using the extended
+    /// symbols `*`, `~`, `$`, `=` and `U` for the values 32 to 36. Other bases ignore this option.
+    pub fn set_checksum_symbol(&mut self, enabled: bool) { self.checksum_symbol = enabled; }
+
+    /// Skips exactly the characters in `characters` when decoding, before the encoded data is
+    /// split into groups.
+    ///
+    /// This allows delimiters like the `.` in `Zm9v.YmFy` to be embedded in the encoded data.
+    /// Every other character that is not part of the alphabet is still an error. Default is empty.
+    pub fn set_ignore_chars(&mut self, characters: &[u8]) {
+        self.ignore_chars = characters.to_vec();
+    }
+
+    /// Accepts only canonical encodings when decoding, as required by **RFC 4648**.
+    ///
+    /// Strict decoding enables the following checks for [`Base64`](Base::Base64),
+    /// [`Base64url`](Base::Base64url), [`Base32`](Base::Base32) and [`Base32hex`](Base::Base32hex):
+    ///
+    /// * [Ignored characters](Settings::set_ignore_chars) are not skipped, so whitespace and all
+    ///   other characters outside of the alphabet are rejected.
+    /// * The last group has to be padded to its full length with the correct number of padding
+    ///   characters.
+    /// * Padding may only appear at the end of the data, so concatenated padded encodings are
+    ///   rejected.
+    /// * The unused bits of the last data character have to be zero.
+    ///
+    /// Default is `false`.
+    pub fn set_strict(&mut self, strict: bool) { self.strict = strict; }
+
+    /// Treats the encoded data as several concatenated messages when decoding.
+    ///
+    /// Every group that contains padding ends a message, so `Zg==YmFy` consists of the messages
+    /// `f` and `bar`. The messages are returned by
+    /// [`into_decoded_messages`](crate::TranslationUnit::into_decoded_messages). Only applies to
+    /// the bases that use padding. Default is `false`.
+    pub fn set_multi_message(&mut self, multi_message: bool) {
+        self.multi_message = multi_message;
+    }
+
+    /// Writes the padding of [`Base64url`](Base::Base64url) as `%3D` instead of `=`.
+    ///
+    /// This keeps padded tokens intact when they are form-encoded as part of a URL. Decoding
+    /// replaces `%3D` with `=` before the data is processed. Other bases ignore this option.
+    /// Default is `false`.
+    pub fn set_percent_encode_padding(&mut self, enabled: bool) {
+        self.percent_encode_padding = enabled;
+    }
+
+    /// Decodes every character in `aliases` as padding, in addition to the padding character of
+    /// the [`Base`](Base).
+    ///
+    /// Some legacy encoders write `.` instead of `=`, so `Zg..` decodes to `f` with `.` as an
+    /// alias. Characters of the alphabet are never treated as padding. Default is empty.
+    pub fn set_pad_aliases(&mut self, aliases: &[u8]) { self.pad_aliases = aliases.to_vec(); }
+
+    /// Appends a line with a digest of the original bytes to the encoded data, so the recipient
+    /// can verify it.
+    ///
+    /// The line has the format `# <algorithm>:<hex>` and is separated from the encoding by a
+    /// line feed, e.g. `# sha256:2c26b4...`. SHA-256 is used when the `sha2` feature is enabled,
+    /// CRC-32 otherwise. Decoding expects such a line after the encoding, removes it and checks
+    /// the digest against the decoded bytes. Default is `false`.
+    pub fn set_digest_line(&mut self, enabled: bool) { self.digest_line = enabled; }
+
+    /// Reads [`Base16`](Base::Base16) encoded data from the output of `xxd` or `hexdump -C`.
+    ///
+    /// The offset column at the start of every line and the ASCII column at its end are removed
+    /// before decoding, so only the hex bytes remain. Other bases ignore this option. Default is
+    /// `false`.
+    pub fn set_hexdump_input(&mut self, enabled: bool) { self.hexdump_input = enabled; }
+
+    /// Experimental: Tries to recover [`Base64`](Base::Base64) or [`Base64url`](Base::Base64url)
+    /// encoded data whose letters have all been converted to the same case.
+    ///
+    /// Every group of four characters is decoded with all combinations of upper- and lowercase
+    /// letters, and the combination whose bytes fit the `hint` best is kept. This is lossy: the
+    /// original bytes can't always be recovered, and groups with several equally good
+    /// combinations are counted by
+    /// [`case_fold_ambiguities`](crate::TranslationUnit::case_fold_ambiguities). Only use it for
+    /// data recovery. Default is [`Disabled`](CaseFold::Disabled).
+    pub fn set_base64_case_fold(&mut self, hint: CaseFold) { self.base64_case_fold = hint; }
+
+    /// Sets the human-readable part of [`Bech32`](Base::Bech32) encoded data, e.g. `bc`.
+    ///
+    /// Encoding requires a human-readable part of 1 to 83 characters in the range 33 to 126 of
+    /// US-ASCII. If it is not empty, decoding also checks that the data starts with it, ignoring
+    /// the case of the letters. Default is empty.
+    pub fn set_bech32_hrp(&mut self, hrp: &str) { self.bech32_hrp = String::from(hrp); }
+
+    /// Adds up to 16 bytes of the encoded data around the position of a decoding error to the
+    /// error, wrapped in [`EncodeError::WithContext`](crate::EncodeError::WithContext).
+    ///
+    /// Non-printable bytes are escaped. This copies a part of the input into the error, so it is
+    /// disabled by default.
+    pub fn set_error_context(&mut self, enabled: bool) { self.error_context = enabled; }
+
+    /// Splits the encoded output into groups of `size` characters, separated by the
+    /// [group separator](Settings::set_group_separator), e.g. `MZXW 6YTB OI== ====` for `foobar`
+    /// as [`Base32`](Base::Base32) with groups of four characters.
+    ///
+    /// Unlike line wrapping, this is meant for key fingerprints that are read by humans. When
+    /// decoding, every occurrence of the group separator is removed before the data is decoded,
+    /// so the separator must not be part of the alphabet. `None` and `Some(0)` disable the
+    /// grouping, which is the default.
+    pub fn set_group_size(&mut self, size: Option<usize>) { self.group_size = size; }
+
+    /// Sets the character that separates the groups of the encoded output if a
+    /// [group size](Settings::set_group_size) is set. Default is a space.
+    pub fn set_group_separator(&mut self, separator: u8) { self.group_separator = separator; }
+
+    /// Decodes data that has been copied from a JSON string, like `"YWI\/Yw=="`.
+    ///
+    /// Before decoding, the surrounding quotes are removed and the escape sequences `\/`, `\\`
+    /// and `\"` are replaced by the escaped character. A quote at only one end of the data fails
+    /// with [`EncodeError::UnmatchedQuote`](crate::EncodeError::UnmatchedQuote). Default is
+    /// `false`.
+    pub fn set_json_unescape(&mut self, enabled: bool) { self.json_unescape = enabled; }
+
+    /// Replaces the alphabet of `base` by the permutation of it that is derived from `seed`, see
+    /// [`Base::seeded_alphabet`](Base::seeded_alphabet).
+    ///
+    /// Encoding and decoding with the same seed round-trips, the padding is not affected. The
+    /// alphabet is only replaced while `base` is the configured [`Base`](Base). **This is
+    /// obfuscation, not encryption**: the permutation can be recovered from a small amount of
+    /// encoded data and must not be used to protect secrets. If `base` has no seeded alphabet,
+    /// [`translate`](crate::TranslationUnit::translate) fails with
+    /// [`EncodeError::UnsupportedBase`](crate::EncodeError::UnsupportedBase). Default is no
+    /// seeded alphabet.
+    pub fn set_seeded_alphabet(&mut self, base: Base, seed: u64) {
+        self.seeded_alphabet = Some((base, seed));
+    }
+
+    /// Accepts the digits `0`, `1` and `8` in [`Base32`](Base::Base32) encoded data as the
+    /// letters `O`, `I` and `B` they are commonly confused with when codes are typed by humans.
+    ///
+    /// None of these digits is part of the `Base32` alphabet, so the replacement is only applied
+    /// to characters that would be invalid otherwise. It is still lossy, as the recovered data
+    /// may differ from the intended one if the typo was a different one. All other bases ignore
+    /// this option. Default is `false`.
+    pub fn set_base32_fuzzy(&mut self, enabled: bool) { self.base32_fuzzy = enabled; }
+
+    /// Joins lines that end with a `\` before decoding, as some legacy formats split encoded
+    /// data this way.
+    ///
+    /// Every `\` that is immediately followed by a line break (`\n` or `\r\n`) is removed
+    /// together with the line break, so groups may be split at any character. Other backslashes
+    /// and line breaks are kept. Default is `false`.
+    pub fn set_line_continuation(&mut self, enabled: bool) { self.line_continuation = enabled; }
+
+    /// Replaces Unicode characters that look like ASCII characters with these before decoding,
+    /// like the fullwidth `＋` (U+FF0B) that rich-text editors and chat apps produce.
+    ///
+    /// The encoded data is read as `UTF-8` first, data that is not valid `UTF-8` is left
+    /// unchanged. All fullwidth forms (U+FF01 to U+FF5E), the small forms of `+`, `-` and `=`,
+    /// the division and fraction slashes, the minus sign and the Unicode hyphens are replaced.
+    /// The positions of decoding errors refer to the replaced data. Default is `false`.
+    pub fn set_normalize_homoglyphs(&mut self, enabled: bool) {
+        self.normalize_homoglyphs = enabled;
+    }
+
+    /// Sets the byte the decoded data is split into records on by
+    /// [`into_decoded_records`](crate::TranslationUnit::into_decoded_records), e.g. `b'\n'` for
+    /// newline-separated records. Default is [`None`], which returns all decoded bytes as a single
+    /// record.
+    pub fn set_output_delimiter(&mut self, delimiter: Option<u8>) {
+        self.output_delimiter = delimiter;
+    }
+
+    /// Keeps the original encoded data unchanged when decoding, so
+    /// [`get_encoded_data`](crate::TranslationUnit::get_encoded_data) returns the input
+    /// byte for byte next to the decoded data, even if it has been decoded with several passes or
+    /// contained characters that have been skipped. Otherwise the encoded data is left as it has
+    /// been decoded in the last pass. Encoding always keeps the original data. Default is `false`.
+    pub fn set_keep_both(&mut self, enabled: bool) { self.keep_both = enabled; }
+
+    /// Sets the order of the bytes in [`Base16`](Base::Base16) encoded data.
+    ///
+    /// With [`LittleEndian`](ByteOrder::LittleEndian) the encoded pairs of hex digits are written
+    /// in reverse order when encoding, so `[0x12, 0x34]` is encoded as `3412`, and read in reverse
+    /// order when decoding, so `3412` is decoded as `[0x12, 0x34]`. The pairs are reversed after
+    /// skipping [ignored characters](Settings::set_ignore_chars) and before inserting a
+    /// [separator](Settings::set_separator). Other bases are not affected. Default is
+    /// [`BigEndian`](ByteOrder::BigEndian).
+    pub fn set_hex_byte_order(&mut self, order: ByteOrder) { self.hex_byte_order = order; }
+
+    /// Swaps the two hex digits of every byte in [`Base16`](Base::Base16) encoded data, so the
+    /// low nibble comes first: `[0x12, 0x34]` is encoded as `2143` and `2143` is decoded as
+    /// `[0x12, 0x34]`. Combined with the [`LittleEndian`](ByteOrder::LittleEndian)
+    /// [byte order](Settings::set_hex_byte_order) the data is encoded as `4321`. Other bases are
+    /// not affected. Default is `false`.
+    pub fn set_hex_nibble_swap(&mut self, enabled: bool) { self.hex_nibble_swap = enabled; }
+
+    /// Accepts encoded data that consists of padded and unpadded messages on separate lines, like
+    /// tokens that have been collected from different sources.
+    ///
+    /// Every line break (`\n` or `\r\n`) ends a message and is removed. If the last group of a
+    /// message or of the whole data is incomplete, the missing padding characters are added, so
+    /// `Zg==\nZm9vYg` is decoded like `Zg==Zm9vYg==`.
+    /// [Ignored characters](Settings::set_ignore_chars) don't count towards the group length. An
+    /// unpadded message can't be followed by another one on the same line. Only the bases with
+    /// padding are affected. The positions of decoding errors refer to the data with the padding
+    /// added. Default is `false`.
+    pub fn set_mixed_padding(&mut self, enabled: bool) { self.mixed_padding = enabled; }
+
+    /// Detects and removes the armor around [`Base64`](Base::Base64) encoded data before
+    /// decoding, so armored files can be decoded without knowing their format.
+    ///
+    /// The first block between a begin and an end line is decoded, all text around it is
+    /// dropped. Recognized are PEM (`-----BEGIN CERTIFICATE-----` ... `-----END CERTIFICATE-----`)
+    /// and PGP (`-----BEGIN PGP MESSAGE-----` ...) armor as well as SSH2 public keys
+    /// (`---- BEGIN SSH2 PUBLIC KEY ----` ...). The `Name: value` header lines at the start of a
+    /// block, including SSH2 headers continued with `\`, the empty line after them and the
+    /// `=XXXX` checksum line of PGP armor are removed as well. Data without a begin line is
+    /// decoded unchanged. Other bases ignore this option. Default is `false`.
+    pub fn set_auto_armor(&mut self, enabled: bool) { self.auto_armor = enabled; }
+
+    /// Makes the encoded data easier to read and type for humans, e.g. to show keys and
+    /// recovery codes, by combining the grouping and case options.
+    ///
+    /// - [`Base64`](Base::Base64), [`Base64url`](Base::Base64url), [`Base32`](Base::Base32),
+    ///   [`Base32hex`](Base::Base32hex) and [`Base16`](Base::Base16) are split into groups of
+    ///   four characters, e.g. `MZXW 6YTB OI== ====`.
+    /// - [`Crockford32`](Base::Crockford32) is split into groups of five characters. Its
+    ///   alphabet has no confusable letters anyway.
+    /// - [`Base32`](Base::Base32) decoding accepts the digits `0`, `1` and `8`, which aren't part
+    ///   of the alphabet, like [fuzzy decoding](Settings::set_base32_fuzzy).
+    /// - [`Lowercase`](Settings::set_lowercase) is ignored, so all case-insensitive bases use
+    ///   uppercase letters.
+    ///
+    /// The groups are separated by the [group separator](Settings::set_group_separator). An
+    /// explicit [group size](Settings::set_group_size) takes precedence over the one above.
+    /// [`Base45`](Base::Base45), whose alphabet contains the space, and the remaining bases
+    /// aren't grouped. Default is `false`.
+    pub fn set_readable(&mut self, enabled: bool) { self.readable = enabled; }
+
+    /// Starts every line of the encoded output with `prefix`, e.g. an indentation to embed the
+    /// data in a YAML block scalar or an INI value.
+    ///
+    /// Encoded data is wrapped into lines by a [group separator](Settings::set_group_separator)
+    /// of `b'\n'`, output without line breaks gets a single prefix. When decoding, the prefix is
+    /// removed from the start of every line that has it before the data is decoded, lines
+    /// without it are kept as they are. An empty prefix, the default, is not added.
+    pub fn set_line_prefix(&mut self, prefix: String) { self.line_prefix = prefix; }
+
+    /// Appends two characters to the encoded data that hold a checksum of the original bytes, so
+    /// typos in short codes like coupon or invite codes can be rejected with
+    /// [`verify_checksum`](Base::verify_checksum).
+    ///
+    /// The checksum is the CRC-32 of the original bytes modulo the square of the size of the
+    /// [alphabet](Base::alphabet), written as two characters of the alphabet with the more
+    /// significant one first. The suffix is appended before the output is formatted, e.g.
+    /// [grouped](Settings::set_group_size). Decoding removes the suffix, decodes the rest and
+    /// fails with [`EncodeError::InvalidChecksumSuffix`] if the suffix doesn't match the decoded
+    /// bytes. Not supported by
+    /// [`ImapUtf7`](Base::ImapUtf7), [`Bech32`](Base::Bech32) and [`Guess`](Base::Guess).
+    /// Default is `false`.
+    pub fn set_checksum_suffix(&mut self, enabled: bool) { self.checksum_suffix = enabled; }
+
+    /// Experimental: Sets the order in which the bits of every group are packed into the encoded
+    /// characters, for data of legacy encoders that don't follow **RFC 4648**.
+    ///
+    /// [`MsbFirst`](BitOrder::MsbFirst) is the standard order. With
+    /// [`LsbFirst`](BitOrder::LsbFirst) the first byte of a group fills the lowest bits and every
+    /// character takes the lowest remaining bits, so `foobar` is encoded as `m92biFmc` in
+    /// [`Base64`](Base::Base64) instead of `Zm9vYmFy`. Padding is the same for both orders. Only
+    /// the bases that split the data into groups of bits support it, which are the `Base64`,
+    /// `Base32` and [`Base16`](Base::Base16) variants; the others fail with
+    /// [`EncodeError::UnsupportedBase`] when the order is not the standard one. Default is
+    /// [`MsbFirst`](BitOrder::MsbFirst).
+    pub fn set_bit_order(&mut self, order: BitOrder) { self.bit_order = order; }
+}
+
+impl Default for Settings {
+    fn default() -> Settings { Settings::new() }
+}
+
+/// Tests for the configuration of translation units.
+#[cfg(any(test, feature = "doc_tests"))]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_chunk_size() {
+        assert_eq!(Base::Base64.chunk_size(), 3);
+        assert_eq!(Base::Base64url.chunk_size(), 3);
+        assert_eq!(Base::Base32.chunk_size(), 5);
+        assert_eq!(Base::Base32hex.chunk_size(), 5);
+        assert_eq!(Base::Base16.chunk_size(), 1);
+        assert_eq!(Base::ImapUtf7.chunk_size(), usize::MAX);
+        assert_eq!(Base::Base45.chunk_size(), 2);
+        assert_eq!(Base::Crockford32.chunk_size(), usize::MAX);
+        assert_eq!(Base::Bech32.chunk_size(), usize::MAX);
+        assert_eq!(Base::FilenameSafe.chunk_size(), 5);
+        assert_eq!(Base::Base32Geohash.chunk_size(), 5);
+        assert_eq!(Base::WebToken.chunk_size(), 3);
+        assert_eq!(Base::Guess.chunk_size(), 30);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_encoded_len() {
+        assert_eq!(Base::Base64.encoded_len(0), Ok(0));
+        assert_eq!(Base::Base64.encoded_len(4), Ok(8));
+        assert_eq!(Base::Base32.encoded_len(6), Ok(16));
+        assert_eq!(Base::Base16.encoded_len(3), Ok(6));
+        assert_eq!(Base::Base45.encoded_len(3), Ok(6));
+        assert_eq!(Base::Crockford32.encoded_len(6), Ok(11));
+        assert_eq!(Base::Bech32.encoded_len(0), Ok(6));
+        assert_eq!(Base::FilenameSafe.encoded_len(6), Ok(10));
+        assert_eq!(Base::Base32Geohash.encoded_len(6), Ok(10));
+        assert_eq!(Base::WebToken.encoded_len(4), Ok(6));
+        assert_eq!(Base::ImapUtf7.encoded_len(2), Ok(6));
+        assert_eq!(Base::Guess.encoded_len(1), Err(EncodeError::UnsupportedBase(Base::Guess)));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_encoded_len_overflow_boundary() {
+        let test_vectors = [(Base::Base64, usize::MAX / 4 * 3), (Base::Base32, usize::MAX / 8 * 5),
+                            (Base::Base16, usize::MAX / 2), (Base::Base45, usize::MAX / 3 * 2),
+                            (Base::Crockford32, usize::MAX / 8), (Base::ImapUtf7, usize::MAX / 3)];
+        for (base, limit) in test_vectors {
+            assert!(base.encoded_len(limit).is_ok());
+            assert_eq!(base.encoded_len(limit + 1),
+                       Err(EncodeError::InputTooLarge { length: limit + 1, limit }));
+            assert!(base.encoded_len(usize::MAX).is_err());
+        }
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_encoded_len_overflow_32_bit() {
+        assert_eq!(Base::Base64.encoded_len(3_221_225_469), Ok(4_294_967_292));
+        assert_eq!(Base::Base64.encoded_len(3_221_225_470),
+                   Err(EncodeError::InputTooLarge { length: 3_221_225_470,
+                                                    limit: 3_221_225_469 }));
+        assert_eq!(Base::Base16.encoded_len(2_147_483_648),
+                   Err(EncodeError::InputTooLarge { length: 2_147_483_648,
+                                                    limit: 2_147_483_647 }));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_decoded_len() {
+        assert_eq!(Base::Base64.decoded_len(0), Ok(0));
+        assert_eq!(Base::Base64.decoded_len(6), Ok(4));
+        assert_eq!(Base::Base64url.decoded_len(8), Ok(6));
+        assert_eq!(Base::Base32hex.decoded_len(16), Ok(10));
+        assert_eq!(Base::Base16.decoded_len(7), Ok(3));
+        assert_eq!(Base::Base45.decoded_len(5), Ok(3));
+        assert_eq!(Base::Crockford32.decoded_len(13), Ok(8));
+        assert_eq!(Base::Bech32.decoded_len(usize::MAX), Ok(usize::MAX / 8 * 5 + 4));
+        assert_eq!(Base::ImapUtf7.decoded_len(8), Ok(9));
+        assert_eq!(Base::Guess.decoded_len(1), Err(EncodeError::UnsupportedBase(Base::Guess)));
+        // Decoding the encoded length yields at least the original length.
+        for base in [Base::Base64, Base::Base32, Base::Base16, Base::Base45] {
+            for len in 0..20 {
+                assert!(base.decoded_len(base.encoded_len(len).unwrap()).unwrap() >= len);
+            }
+        }
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_expansion_ratio() {
+        assert_eq!(Base::Base64.expansion_ratio(), 4.0 / 3.0);
+        assert_eq!(Base::Base64url.expansion_ratio(), 4.0 / 3.0);
+        assert_eq!(Base::Base32.expansion_ratio(), 1.6);
+        assert_eq!(Base::Base32hex.expansion_ratio(), 1.6);
+        assert_eq!(Base::Base16.expansion_ratio(), 2.0);
+        assert_eq!(Base::Base45.expansion_ratio(), 1.5);
+        assert_eq!(Base::Crockford32.expansion_ratio(), 1.6);
+        assert_eq!(Base::Bech32.expansion_ratio(), 1.6);
+        assert_eq!(Base::FilenameSafe.expansion_ratio(), 1.6);
+        assert_eq!(Base::Base32Geohash.expansion_ratio(), 1.6);
+        assert_eq!(Base::WebToken.expansion_ratio(), 4.0 / 3.0);
+        assert_eq!(Base::ImapUtf7.expansion_ratio(), 1.0);
+        assert!(Base::Guess.expansion_ratio().is_nan());
+        // The ratio is the limit of the encoded length per byte.
+        for base in [Base::Base64, Base::Base32, Base::Base16, Base::Base45] {
+            let len = 3000 * base.chunk_size();
+            assert_eq!(base.encoded_len(len).unwrap() as f64 / len as f64, base.expansion_ratio());
+        }
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_alphabet() {
+        assert_eq!(Base::Base64.alphabet().map(str::len), Some(64));
+        assert_eq!(Base::Base64url.alphabet().map(|alphabet| &alphabet[62..]), Some("-_"));
+        assert_eq!(Base::Base32.alphabet().map(str::len), Some(32));
+        assert_eq!(Base::Base32hex.alphabet().map(str::len), Some(32));
+        assert_eq!(Base::Crockford32.alphabet().map(str::len), Some(32));
+        assert_eq!(Base::Bech32.alphabet().map(str::len), Some(32));
+        assert_eq!(Base::FilenameSafe.alphabet().map(str::len), Some(32));
+        assert_eq!(Base::Base32Geohash.alphabet().map(str::len), Some(32));
+        assert_eq!(Base::Guess.alphabet(), None);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_invalid_positions() {
+        assert_eq!(Base::Base64.invalid_positions(b"Zm9vYmFy"), Vec::<usize>::new());
+        assert_eq!(Base::Base64.invalid_positions(b"-Zm9v Ym_Fy=="), vec![0, 5, 8]);
+        assert_eq!(Base::Base64url.invalid_positions(b"-Zm9v Ym_Fy=="), vec![5]);
+        assert_eq!(Base::Base32.invalid_positions(b"MZXW0YTB1I======"), vec![4, 8]);
+        assert_eq!(Base::Base32hex.invalid_positions(b"CPNMUOJ1E8======"), Vec::<usize>::new());
+        assert_eq!(Base::Base16.invalid_positions(b"66:6f:6F"), vec![2, 4, 5]);
+        assert_eq!(Base::Base45.invalid_positions(b"BB8=%69 VD"), vec![3]);
+        assert_eq!(Base::Guess.invalid_positions(b"Zm9v\tYmFy\x00"), vec![4, 9]);
+        assert_eq!(Base::FilenameSafe.invalid_positions(b"cPnw=z"), vec![3, 4, 5]);
+        assert_eq!(Base::Base32Geohash.invalid_positions(b"u4pRaIlO"), vec![4, 5, 6, 7]);
+        assert_eq!(Base::WebToken.invalid_positions(b"+/-_ Zm8=.%"), vec![9, 10]);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_is_confusable_free() {
+        // Every look-alike is decoded as the character of the alphabet.
+        assert!(Base::Crockford32.is_confusable_free());
+        // `O` is in the alphabet, but a mistyped `0` is rejected.
+        assert!(!Base::Base32.is_confusable_free());
+        assert!(!Base::Base16.is_confusable_free());
+        assert!(!Base::Base32Geohash.is_confusable_free());
+        // `0` and `O`, `1` and `I` or `o` and `l` next to the digits.
+        assert!(!Base::Base32hex.is_confusable_free());
+        assert!(!Base::Base64.is_confusable_free());
+        assert!(!Base::FilenameSafe.is_confusable_free());
+        assert!(!Base::Base45.is_confusable_free());
+        assert!(!Base::Guess.is_confusable_free());
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_invalid_positions_crockford32() {
+        assert_eq!(Base::Crockford32.invalid_positions(b"1O-il-ABU"), Vec::<usize>::new());
+        assert_eq!(Base::Crockford32.invalid_positions(b"AU*B$C*"), vec![1, 2, 4]);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_invalid_positions_bech32() {
+        assert_eq!(Base::Bech32.invalid_positions(b"A12UEL5L"), Vec::<usize>::new());
+        assert_eq!(Base::Bech32.invalid_positions(b"a b12ueL5bio"), vec![1, 9, 10, 11]);
+        assert_eq!(Base::Bech32.invalid_positions(b"a\x7Fbc"), vec![1]);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_invalid_positions_imap_utf7() {
+        assert_eq!(Base::ImapUtf7.invalid_positions(b"a b&AOk-c"), Vec::<usize>::new());
+        assert_eq!(Base::ImapUtf7.invalid_positions(b"a\tb&A/k_-c\x80"), vec![1, 5, 7, 10]);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_seeded_alphabet() {
+        for base in [Base::Base64, Base::Base64url, Base::Base32, Base::Base32hex, Base::Base16,
+                     Base::Crockford32, Base::Base45] {
+            let alphabet = base.seeded_alphabet(42).unwrap();
+            assert_eq!(alphabet, base.seeded_alphabet(42).unwrap());
+            assert_ne!(alphabet, base.seeded_alphabet(43).unwrap());
+            assert_ne!(alphabet, base.alphabet().unwrap());
+            let mut sorted = alphabet.into_bytes();
+            sorted.sort_unstable();
+            let mut expected = base.alphabet().unwrap().as_bytes().to_vec();
+            expected.sort_unstable();
+            assert_eq!(sorted, expected);
+        }
+        assert_eq!(Base::ImapUtf7.seeded_alphabet(42), None);
+        assert_eq!(Base::Bech32.seeded_alphabet(42), None);
+        assert_eq!(Base::Guess.seeded_alphabet(42), None);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_padding() {
+        assert_eq!(Base::Base64.padding(), Some('='));
+        assert_eq!(Base::Base32hex.padding(), Some('='));
+        assert_eq!(Base::Base16.padding(), None);
+        assert_eq!(Base::ImapUtf7.padding(), None);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_align_chunks() {
+        let data: Vec<u8> = (0..17).collect();
+        let chunks: Vec<&[u8]> = Base::Base32.align_chunks(&data, 12).collect();
+        assert_eq!(chunks, vec![&data[0..10], &data[10..17]]);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_align_chunks_approx_below_chunk_size() {
+        let data: Vec<u8> = (0..7).collect();
+        let chunks: Vec<&[u8]> = Base::Base64.align_chunks(&data, 1).collect();
+        assert_eq!(chunks, vec![&data[0..3], &data[3..6], &data[6..7]]);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_align_chunks_imap_utf7() {
+        let data: Vec<u8> = (0..7).collect();
+        let chunks: Vec<&[u8]> = Base::ImapUtf7.align_chunks(&data, 3).collect();
+        assert_eq!(chunks, vec![&data[..]]);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_base_align_chunks_empty() {
+        assert_eq!(Base::Base16.align_chunks(&[], 8).count(), 0);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_settings_round_trip() {
+        let mut settings = Settings::new();
+        settings.set_base(Base::Base32hex);
+        settings.set_encode_mode(EncodeMode::Decode);
+        settings.set_output_block_align(Some(16));
+        settings.set_max_input_len(Some(1024));
+        settings.set_lowercase(true);
+        settings.set_separator(Some(b':'));
+        settings.set_expected_output_len(Some(32));
+        settings.set_decode_passes(3);
+        settings.set_checksum_symbol(true);
+        settings.set_ignore_chars(b".-");
+        settings.set_strict(true);
+        settings.set_multi_message(true);
+        settings.set_percent_encode_padding(true);
+        settings.set_pad_aliases(b".");
+        settings.set_digest_line(true);
+        settings.set_hexdump_input(true);
+        settings.set_base64_case_fold(CaseFold::LowercaseText);
+        settings.set_bech32_hrp("bc");
+        settings.set_error_context(true);
+        settings.set_group_size(Some(4));
+        settings.set_group_separator(b'-');
+        settings.set_json_unescape(true);
+        settings.set_seeded_alphabet(Base::Base32hex, 42);
+        settings.set_base32_fuzzy(true);
+        settings.set_line_continuation(true);
+        settings.set_normalize_homoglyphs(true);
+        settings.set_output_delimiter(Some(b'\n'));
+        settings.set_keep_both(true);
+        settings.set_hex_byte_order(ByteOrder::LittleEndian);
+        settings.set_hex_nibble_swap(true);
+        settings.set_mixed_padding(true);
+        settings.set_auto_armor(true);
+        settings.set_readable(true);
+        settings.set_line_prefix(String::from("  "));
+        settings.set_checksum_suffix(true);
+        settings.set_bit_order(BitOrder::LsbFirst);
+
+        assert_eq!(settings.base(), Base::Base32hex);
+        assert_eq!(settings.encode_mode(), EncodeMode::Decode);
+        assert_eq!(settings.output_block_align(), Some(16));
+        assert_eq!(settings.max_input_len(), Some(1024));
+        assert!(settings.lowercase());
+        assert_eq!(settings.separator(), Some(b':'));
+        assert_eq!(settings.expected_output_len(), Some(32));
+        assert_eq!(settings.decode_passes(), 3);
+        assert!(settings.checksum_symbol());
+        assert_eq!(settings.ignore_chars(), b".-");
+        assert!(settings.strict());
+        assert!(settings.multi_message());
+        assert!(settings.percent_encode_padding());
+        assert_eq!(settings.pad_aliases(), b".");
+        assert!(settings.digest_line());
+        assert!(settings.hexdump_input());
+        assert_eq!(settings.base64_case_fold(), CaseFold::LowercaseText);
+        assert_eq!(settings.bech32_hrp(), "bc");
+        assert!(settings.error_context());
+        assert_eq!(settings.group_size(), Some(4));
+        assert_eq!(settings.group_separator(), b'-');
+        assert!(settings.json_unescape());
+        assert_eq!(settings.seeded_alphabet(), Some((Base::Base32hex, 42)));
+        assert!(settings.base32_fuzzy());
+        assert!(settings.line_continuation());
+        assert!(settings.normalize_homoglyphs());
+        assert_eq!(settings.output_delimiter(), Some(b'\n'));
+        assert!(settings.keep_both());
+        assert_eq!(settings.hex_byte_order(), ByteOrder::LittleEndian);
+        assert!(settings.hex_nibble_swap());
+        assert!(settings.mixed_padding());
+        assert!(settings.auto_armor());
+        assert!(settings.readable());
+        assert_eq!(settings.line_prefix(), "  ");
+        assert!(settings.checksum_suffix());
+        assert_eq!(settings.bit_order(), BitOrder::LsbFirst);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_settings_default_values() {
+        let settings = Settings::new();
+        assert_eq!(settings.base(), Base::Guess);
+        assert_eq!(settings.encode_mode(), EncodeMode::Encode);
+        assert_eq!(settings.output_block_align(), None);
+        assert_eq!(settings.max_input_len(), None);
+        assert!(!settings.lowercase());
+        assert_eq!(settings.separator(), None);
+        assert_eq!(settings.expected_output_len(), None);
+        assert_eq!(settings.decode_passes(), 1);
+        assert!(!settings.checksum_symbol());
+        assert_eq!(settings.ignore_chars(), b"");
+        assert!(!settings.strict());
+        assert!(!settings.multi_message());
+        assert!(!settings.percent_encode_padding());
+        assert_eq!(settings.pad_aliases(), b"");
+        assert!(!settings.digest_line());
+        assert!(!settings.hexdump_input());
+        assert_eq!(settings.base64_case_fold(), CaseFold::Disabled);
+        assert_eq!(settings.bech32_hrp(), "");
+        assert!(!settings.error_context());
+        assert_eq!(settings.group_size(), None);
+        assert_eq!(settings.group_separator(), b' ');
+        assert!(!settings.json_unescape());
+        assert_eq!(settings.seeded_alphabet(), None);
+        assert!(!settings.base32_fuzzy());
+        assert!(!settings.line_continuation());
+        assert!(!settings.normalize_homoglyphs());
+        assert_eq!(settings.output_delimiter(), None);
+        assert!(!settings.keep_both());
+        assert_eq!(settings.hex_byte_order(), ByteOrder::BigEndian);
+        assert!(!settings.hex_nibble_swap());
+        assert!(!settings.mixed_padding());
+        assert!(!settings.auto_armor());
+        assert!(!settings.readable());
+        assert_eq!(settings.line_prefix(), "");
+        assert!(!settings.checksum_suffix());
+        assert_eq!(settings.bit_order(), BitOrder::MsbFirst);
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_settings_eq() {
+        let mut settings = Settings::new();
+        settings.set_ignore_chars(b".");
+        let mut other = settings.clone();
+        assert_eq!(settings, other);
+        other.set_ignore_chars(b",");
+        assert_ne!(settings, other);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_settings_from_spec() {
+        let settings = Settings::from_spec("base64/decode").unwrap();
+        assert_eq!(settings.base(), Base::Base64);
+        assert_eq!(settings.encode_mode(), EncodeMode::Decode);
+
+        let settings = Settings::from_spec("Base32hex/Encode").unwrap();
+        assert_eq!(settings.base(), Base::Base32hex);
+        assert_eq!(settings.encode_mode(), EncodeMode::Encode);
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_settings_from_spec_unknown_base() {
+        assert_eq!(Settings::from_spec("base63/decode").err(),
+                   Some(EncodeError::UnknownBase(String::from("base63"))));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_settings_from_spec_unknown_mode() {
+        assert_eq!(Settings::from_spec("base64/translate").err(),
+                   Some(EncodeError::UnknownEncodeMode(String::from("translate"))));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_settings_from_spec_missing_slash() {
+        assert_eq!(Settings::from_spec("base64").err(),
+                   Some(EncodeError::InvalidSpec(String::from("base64"))));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_settings_from_spec_empty_parts() {
+        assert_eq!(Settings::from_spec("/decode").err(),
+                   Some(EncodeError::UnknownBase(String::new())));
+        assert_eq!(Settings::from_spec("base64/").err(),
+                   Some(EncodeError::UnknownEncodeMode(String::new())));
+    }
 }
- 
@@ -0,0 +1,332 @@
+/* Copyright (C) 2022  Fabian Moos
+ * This file is part of encodex.
+ *
+ * encodex is free software: you can redistribute it and/or modify it under the terms of the GNU
+ * General Public License as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * encodex is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without
+ * even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+ * General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along with encodex. If not,
+ * see <https://www.gnu.org/licenses/>.
+ */
+
+
+//! Incremental conversion of encoded data from one base to another.
+
+use crate::encoding::Encoding;
+use crate::error::EncodeError;
+use crate::settings::Base;
+
+/// Converts data encoded as one [`Base`](crate::Base) to another one piece by piece, so large
+/// inputs can be converted with bounded memory.
+///
+/// Every call of [`update`](Transcoder::update) decodes the complete groups of the input that has
+/// been seen so far and encodes the complete groups of the decoded bytes, the rest is buffered
+/// until the next call. The last encoded group is always held back, because it may be padded.
+/// [`finalize`](Transcoder::finalize) translates the buffered rest. Both bases are used with the
+/// default [`Settings`](crate::Settings), so the input must not contain line breaks or other
+/// characters outside of the alphabet. After an error the transcoder should be discarded.
+///
+/// Instead of the built-in bases, [custom encodings](crate::Encoding) that en- and decode their
+/// data [group by group](crate::Encoding::group_len) can be converted with
+/// [`with_encodings`](Transcoder::with_encodings).
+///
+/// # Usage Example
+///
+/// ```
+/// use encodex::{Base, Transcoder};
+///
+/// let mut transcoder = Transcoder::new(Base::Base64, Base::Base32).unwrap();
+/// let mut output = transcoder.update(b"Zm9vY").unwrap();
+/// output.extend(transcoder.update(b"mFy").unwrap());
+/// output.extend(transcoder.finalize().unwrap());
+///
+/// assert_eq!(output, b"MZXW6YTBOI======");
+/// ```
+pub struct Transcoder {
+    from_base: Option<Base>,
+    to_base: Option<Base>,
+    /// The encoding the input is decoded with.
+    from_encoding: Box<dyn Encoding>,
+    /// The encoding the output is encoded with.
+    to_encoding: Box<dyn Encoding>,
+    /// The number of characters of a group of the input.
+    from_group_len: usize,
+    /// The number of bytes of a group of the output.
+    to_group_len: usize,
+    /// Encoded input that has not been decoded yet.
+    encoded_data: Vec<u8>,
+    /// Decoded bytes that have not been encoded yet.
+    decoded_data: Vec<u8>,
+    /// The number of input characters that have been decoded so far.
+    offset: usize,
+}
+
+impl Transcoder {
+    /// Creates a [`Transcoder`] that decodes data encoded as `from_base` and encodes it as
+    /// `to_base`.
+    ///
+    /// Fails with [`EncodeError::UnsupportedBase`](crate::EncodeError::UnsupportedBase) for the
+    /// bases without a fixed group size, which are [`ImapUtf7`](crate::Base::ImapUtf7),
+    /// [`Crockford32`](crate::Base::Crockford32), [`Bech32`](crate::Base::Bech32) and
+    /// [`Guess`](crate::Base::Guess).
+    pub fn new(from_base: Base, to_base: Base) -> Result<Transcoder, EncodeError> {
+        for base in [from_base, to_base] {
+            if base.group_len().is_none() { return Err(EncodeError::UnsupportedBase(base)); }
+        }
+        let mut transcoder = Transcoder::with_encodings(Box::new(from_base), Box::new(to_base))?;
+        transcoder.from_base = Some(from_base);
+        transcoder.to_base = Some(to_base);
+        Ok(transcoder)
+    }
+
+    /// Creates a [`Transcoder`] that decodes data with the custom `from_encoding` and encodes it
+    /// with the custom `to_encoding`.
+    ///
+    /// Fails with [`EncodeError::UngroupedEncoding`](crate::EncodeError::UngroupedEncoding) if
+    /// one of the encodings doesn't report its [group length](crate::Encoding::group_len).
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// use encodex::{Base, EncodeError, Encoding, Transcoder};
+    ///
+    /// /// Encodes every byte as two letters from `a` to `p`.
+    /// struct Letters;
+    ///
+    /// impl Encoding for Letters {
+    ///     fn encode(&self, data: &[u8]) -> Result<Vec<u8>, EncodeError> {
+    ///         Ok(data.iter().flat_map(|byte| [b'a' + (byte >> 4), b'a' + (byte & 15)]).collect())
+    ///     }
+    ///     fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, EncodeError> {
+    ///         Ok(encoded.chunks(2).map(|pair| (pair[0] - b'a') << 4 | (pair[1] - b'a')).collect())
+    ///     }
+    ///     fn group_len(&self) -> Option<(usize, usize)> { Some((1, 2)) }
+    /// }
+    ///
+    /// let mut transcoder = Transcoder::with_encodings(Box::new(Base::Base64),
+    ///                                                 Box::new(Letters)).unwrap();
+    /// let mut output = transcoder.update(b"Zm9").unwrap();
+    /// output.extend(transcoder.update(b"v").unwrap());
+    /// output.extend(transcoder.finalize().unwrap());
+    ///
+    /// assert_eq!(output, b"gggpgp");
+    /// ```
+    pub fn with_encodings(from_encoding: Box<dyn Encoding>, to_encoding: Box<dyn Encoding>)
+                          -> Result<Transcoder, EncodeError> {
+        let (from_group_len, to_group_len) = match (from_encoding.group_len(),
+                                                    to_encoding.group_len()) {
+            (Some((_, from_group_len)), Some((to_group_len, _))) => {
+                (from_group_len, to_group_len)
+            }
+            _ => { return Err(EncodeError::UngroupedEncoding); }
+        };
+        Ok(Transcoder {
+            from_base: None,
+            to_base: None,
+            from_encoding,
+            to_encoding,
+            from_group_len,
+            to_group_len,
+            encoded_data: Vec::new(),
+            decoded_data: Vec::new(),
+            offset: 0,
+        })
+    }
+
+    /// Returns the [`Base`](crate::Base) the input is decoded as, or [`None`] for a custom
+    /// encoding.
+    pub fn from_base(&self) -> Option<Base> { self.from_base }
+
+    /// Returns the [`Base`](crate::Base) the output is encoded as, or [`None`] for a custom
+    /// encoding.
+    pub fn to_base(&self) -> Option<Base> { self.to_base }
+
+    /// Adds `input` to the encoded data and returns the part of the output that is complete.
+    ///
+    /// The positions of decoding errors refer to the whole input of all calls.
+    pub fn update(&mut self, input: &[u8]) -> Result<Vec<u8>, EncodeError> {
+        self.encoded_data.extend_from_slice(input);
+        let group_len = self.from_group_len;
+        let mut decode_len = self.encoded_data.len() / group_len * group_len;
+        if decode_len == self.encoded_data.len() {
+            decode_len = decode_len.saturating_sub(group_len);
+        }
+        self.decode(decode_len)?;
+        self.encode(false)
+    }
+
+    /// Translates the buffered rest of the input and returns the end of the output.
+    pub fn finalize(mut self) -> Result<Vec<u8>, EncodeError> {
+        self.decode(self.encoded_data.len())?;
+        self.encode(true)
+    }
+
+    /// Decodes the first `len` characters of the buffered input.
+    fn decode(&mut self, len: usize) -> Result<(), EncodeError> {
+        if len == 0 { return Ok(()); }
+        let decoded_data = self.from_encoding.decode(&self.encoded_data[..len])
+                                             .map_err(|error| error.offset_position(self.offset))?;
+        self.decoded_data.extend_from_slice(&decoded_data);
+        self.encoded_data.drain(..len);
+        self.offset += len;
+        Ok(())
+    }
+
+    /// Encodes the complete groups of the buffered decoded bytes, or all of them if `last` is
+    /// set.
+    fn encode(&mut self, last: bool) -> Result<Vec<u8>, EncodeError> {
+        let len = if last {
+            self.decoded_data.len()
+        } else {
+            self.decoded_data.len() / self.to_group_len * self.to_group_len
+        };
+        let encoded = self.to_encoding.encode(&self.decoded_data[..len])?;
+        self.decoded_data.drain(..len);
+        Ok(encoded)
+    }
+}
+
+/// Tests for the incremental conversion between bases.
+#[cfg(any(test, feature = "doc_tests"))]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "base64", feature = "base32", feature = "base16", feature = "base45"))]
+    use crate::base_encoding::encode_to_string;
+
+    /// Feeds `input` to a new [`Transcoder`] in pieces of `piece_len` bytes.
+    #[cfg(feature = "base64")]
+    fn transcode_in_pieces(from_base: Base, to_base: Base, input: &[u8], piece_len: usize)
+                           -> Result<Vec<u8>, EncodeError> {
+        let mut transcoder = Transcoder::new(from_base, to_base)?;
+        let mut output = Vec::new();
+        for piece in input.chunks(piece_len) { output.extend(transcoder.update(piece)?); }
+        output.extend(transcoder.finalize()?);
+        Ok(output)
+    }
+
+    #[cfg(all(feature = "base64", feature = "base32", feature = "base16", feature = "base45"))]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_transcoder_matches_one_shot() {
+        let data: Vec<u8> = (0..=255).cycle().take(300).collect();
+        let bases = [Base::Base64, Base::Base64url, Base::Base32, Base::Base32hex, Base::Base16,
+                     Base::Base45];
+        for from_base in bases {
+            let input = encode_to_string(&data, from_base).unwrap();
+            for to_base in bases {
+                let expected = encode_to_string(&data, to_base).unwrap().into_bytes();
+                for piece_len in [1, 2, 3, 7, 64, 5000] {
+                    assert_eq!(transcode_in_pieces(from_base, to_base, input.as_bytes(),
+                                                   piece_len),
+                               Ok(expected.clone()));
+                }
+            }
+        }
+    }
+
+    #[cfg(all(feature = "base64", feature = "base16"))]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_transcoder_holds_back_partial_groups() {
+        let mut transcoder = Transcoder::new(Base::Base64, Base::Base16).unwrap();
+        assert_eq!(transcoder.from_base(), Some(Base::Base64));
+        assert_eq!(transcoder.to_base(), Some(Base::Base16));
+        assert_eq!(transcoder.update(b"Zm9"), Ok(Vec::new()));
+        assert_eq!(transcoder.update(b"v"), Ok(Vec::new()));
+        assert_eq!(transcoder.update(b"Y"), Ok(b"666F6F".to_vec()));
+        assert_eq!(transcoder.update(b"g=="), Ok(Vec::new()));
+        assert_eq!(transcoder.finalize(), Ok(b"62".to_vec()));
+    }
+
+    #[cfg(feature = "base64")]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_transcoder_empty() {
+        assert_eq!(transcode_in_pieces(Base::Base32, Base::Base64, b"", 1), Ok(Vec::new()));
+    }
+
+    #[cfg(all(feature = "base64", feature = "base32"))]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_transcoder_error_position() {
+        assert_eq!(transcode_in_pieces(Base::Base64, Base::Base32, b"Zm9vYmFyZm!v", 5),
+                   Err(EncodeError::InvalidCharacter { base: Base::Base64,
+                                                       character: b'!',
+                                                       position: 10 }));
+    }
+
+    #[cfg(all(feature = "base64", feature = "base16"))]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_transcoder_concatenated_padded_data() {
+        // Like a single decode, padding may end every group.
+        assert_eq!(transcode_in_pieces(Base::Base64, Base::Base16, b"Zg==Zm9v", 4),
+                   Ok(b"66666F6F".to_vec()));
+    }
+
+    #[cfg(all(feature = "base64", feature = "base16"))]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_transcoder_incomplete_input() {
+        assert_eq!(transcode_in_pieces(Base::Base64, Base::Base16, b"Zm9vY", 2),
+                   Err(EncodeError::IncompleteGroup { got: 1, needed: 4 }));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_transcoder_unsupported_base() {
+        assert!(matches!(Transcoder::new(Base::ImapUtf7, Base::Base64),
+                         Err(EncodeError::UnsupportedBase(Base::ImapUtf7))));
+        assert!(matches!(Transcoder::new(Base::Base64, Base::Crockford32),
+                         Err(EncodeError::UnsupportedBase(Base::Crockford32))));
+    }
+    /// Encodes every byte as its value plus one, in groups of two bytes.
+    #[cfg(feature = "base64")]
+    struct Shifted;
+
+    #[cfg(feature = "base64")]
+    impl Encoding for Shifted {
+        fn encode(&self, data: &[u8]) -> Result<Vec<u8>, EncodeError> {
+            Ok(data.iter().map(|byte| byte.wrapping_add(1)).collect())
+        }
+
+        fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, EncodeError> {
+            Ok(encoded.iter().map(|byte| byte.wrapping_sub(1)).collect())
+        }
+
+        fn group_len(&self) -> Option<(usize, usize)> { Some((2, 2)) }
+    }
+
+    /// Encodes every byte as itself, without a group length.
+    struct Identity;
+
+    impl Encoding for Identity {
+        fn encode(&self, data: &[u8]) -> Result<Vec<u8>, EncodeError> { Ok(data.to_vec()) }
+        fn decode(&self, encoded: &[u8]) -> Result<Vec<u8>, EncodeError> { Ok(encoded.to_vec()) }
+    }
+
+    #[cfg(feature = "base64")]
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_transcoder_custom_encodings() {
+        let mut transcoder = Transcoder::with_encodings(Box::new(Shifted),
+                                                        Box::new(Base::Base64)).unwrap();
+        assert_eq!(transcoder.from_base(), None);
+        assert_eq!(transcoder.to_base(), None);
+        let mut output = transcoder.update(b"gp").unwrap();
+        output.extend(transcoder.update(b"pcb").unwrap());
+        output.extend(transcoder.update(b"s").unwrap());
+        output.extend(transcoder.finalize().unwrap());
+        assert_eq!(output, b"Zm9vYmFy");
+
+        let mut transcoder = Transcoder::with_encodings(Box::new(Base::Base64),
+                                                        Box::new(Shifted)).unwrap();
+        assert_eq!(transcoder.update(b"Zm9vYmFy"), Ok(b"gp".to_vec()));
+        assert_eq!(transcoder.finalize(), Ok(b"pcbs".to_vec()));
+    }
+
+    #[cfg_attr(not(feature = "doc_tests"), test)]
+    fn test_transcoder_ungrouped_encoding() {
+        assert!(matches!(Transcoder::with_encodings(Box::new(Identity), Box::new(Base::Base64)),
+                         Err(EncodeError::UngroupedEncoding)));
+        assert!(matches!(Transcoder::with_encodings(Box::new(Base::Base64),
+                                                    Box::new(Base::Bech32)),
+                         Err(EncodeError::UngroupedEncoding)));
+    }
+}
@@ -0,0 +1,152 @@
+/* Copyright (C) 2022  Fabian Moos
+ * This file is part of encodex.
+ *
+ * encodex is free software: you can redistribute it and/or modify it under the terms of the GNU
+ * General Public License as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * encodex is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without
+ * even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+ * General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License along with encodex. If not,
+ * see <https://www.gnu.org/licenses/>.
+ */
+
+//! Tests that run the `encodex` binary and check its exit code and output.
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+#[cfg(feature = "base64")]
+use std::{io::Write, process::Stdio, thread};
+
+fn fixture_path(name: &str) -> PathBuf {
+    let mut file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    file_path.push("tests/fixtures");
+    file_path.push(name);
+    file_path
+}
+
+fn run_encodex(args: &[&str], files: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_encodex")).args(args)
+                                               .args(files.iter().map(|file| fixture_path(file)))
+                                               .output()
+                                               .unwrap()
+}
+
+/// Runs `encodex` with `stdin` piped to its standard input. The input is written from another
+/// thread, so a large output can't block it.
+#[cfg(feature = "base64")]
+fn run_encodex_with_stdin(args: &[&str], stdin: Vec<u8>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_encodex")).args(args)
+                                                               .stdin(Stdio::piped())
+                                                               .stdout(Stdio::piped())
+                                                               .stderr(Stdio::piped())
+                                                               .spawn()
+                                                               .unwrap();
+    let mut pipe = child.stdin.take().unwrap();
+    let writer = thread::spawn(move || pipe.write_all(&stdin));
+    let output = child.wait_with_output().unwrap();
+    writer.join().unwrap().unwrap();
+    output
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn test_check_valid_file() {
+    let output = run_encodex(&["-d", "--check", "-b", "Base64"], &["foobar.b64"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_check_corrupted_file() {
+    let output = run_encodex(&["-d", "--check", "-b", "Base64"], &["corrupt.b64"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(!output.stderr.is_empty());
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn test_fail_fast_stops_at_first_error() {
+    // The inputs are translated from the last to the first one.
+    let output = run_encodex(&["-d", "-b", "Base64"], &["foobar.b64", "corrupt.b64"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+
+    let output = run_encodex(&["-d", "-b", "Base64"], &["corrupt.b64", "foobar.b64"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(output.stdout, b"foobar\n");
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn test_keep_going_translates_all_inputs() {
+    let output = run_encodex(&["-d", "--keep-going", "-b", "Base64"],
+                             &["foobar.b64", "corrupt.b64"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(output.stdout, b"foobar\n");
+    assert!(!output.stderr.is_empty());
+
+    let output = run_encodex(&["-d", "--keep-going", "-b", "Base64"],
+                             &["foobar.b64", "foobar.b64"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, b"foobar\nfoobar\n");
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn test_labeled_output() {
+    let output = run_encodex(&["--labeled", "-b", "Base64"], &["foobar.txt", "foobar.b64"]);
+    assert_eq!(output.status.code(), Some(0));
+    let expected = format!("# {}\nWm05dlltRnkK\n# {}\nZm9vYmFy\n",
+                           fixture_path("foobar.b64").display(),
+                           fixture_path("foobar.txt").display());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+
+    let output = run_encodex(&["-b", "Base64"], &["foobar.txt", "foobar.b64"]);
+    assert_eq!(output.stdout, b"Wm05dlltRnkK\nZm9vYmFy\n");
+}
+
+#[cfg(all(feature = "base64", feature = "base32", feature = "base16", feature = "base45"))]
+#[test]
+fn test_stdin_filter_large_input() {
+    // Several blocks of stdin, the last one is incomplete.
+    let data: Vec<u8> = (0..1_000_003u32).map(|index| index.wrapping_mul(2_654_435_761) >> 24)
+                                         .map(|byte| byte as u8)
+                                         .collect();
+    for base in ["Base64", "Base32", "Base16", "Base45"] {
+        let encoded = run_encodex_with_stdin(&["-b", base], data.clone());
+        assert_eq!(encoded.status.code(), Some(0));
+        // The line break at the end of the encoded output is stripped again.
+        let decoded = run_encodex_with_stdin(&["-d", "-b", base], encoded.stdout);
+        assert_eq!(decoded.status.code(), Some(0));
+        assert_eq!(decoded.stdout, data);
+    }
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn test_stdin_filter_error_position() {
+    let mut encoded = b"Zm9v".repeat(50_000);
+    encoded[150_001] = b'!';
+    let output = run_encodex_with_stdin(&["-d", "-b", "Base64"], encoded);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("position 150001"), "{}", stderr);
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn test_keep_going_stops_at_failed_stdin_block() {
+    // The second of three blocks of stdin is corrupted.
+    let mut encoded = b"Zm9v".repeat(3 * 16_384);
+    encoded[100_001] = b'!';
+    let output = run_encodex_with_stdin(&["-d", "--keep-going", "-b", "Base64"], encoded);
+    assert_eq!(output.status.code(), Some(1));
+    // Only the output of the first block is written, the third block isn't translated.
+    assert_eq!(output.stdout, b"foo".repeat(16_384));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("position 100001"), "{}", stderr);
+}